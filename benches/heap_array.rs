@@ -0,0 +1,80 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use little_collections::heap_array::HeapArray;
+use std::hint::black_box;
+
+const LEN: usize = 1 << 16;
+
+fn from_exact_iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_exact_iter");
+    group.bench_function("from_exact_iter", |b| {
+        b.iter(|| HeapArray::from_exact_iter((0..black_box(LEN)).map(|i| i as u64)))
+    });
+    group.bench_function("from_iter", |b| {
+        b.iter(|| (0..black_box(LEN)).map(|i| i as u64).collect::<HeapArray<u64>>())
+    });
+    group.finish();
+}
+
+fn indexing(c: &mut Criterion) {
+    let array = HeapArray::from_exact_iter((0..LEN).map(|i| i as u64));
+    let mut group = c.benchmark_group("indexing");
+    group.bench_function("index", |b| {
+        b.iter(|| {
+            let array = black_box(&array);
+            (0..array.len()).map(|i| array[i]).sum::<u64>()
+        })
+    });
+    group.bench_function("get_unchecked", |b| {
+        b.iter(|| {
+            let array = black_box(&array);
+            (0..array.len()).map(|i| unsafe {*array.get_unchecked(i)}).sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+fn fold(c: &mut Criterion) {
+    let array = HeapArray::from_exact_iter((0..LEN).map(|i| i as u64));
+    let mut group = c.benchmark_group("fold");
+    group.bench_function("fold", |b| {
+        b.iter(|| black_box(&array).fold(0u64, |acc, &x| acc.wrapping_add(x)))
+    });
+    group.bench_function("iter_fold", |b| {
+        b.iter(|| black_box(&array).iter().fold(0u64, |acc, &x| acc.wrapping_add(x)))
+    });
+    group.finish();
+}
+
+fn enumerate_mut(c: &mut Criterion) {
+    let mut array = HeapArray::from_exact_iter((0..LEN).map(|i| i as u64));
+    let mut group = c.benchmark_group("enumerate_mut");
+    group.bench_function("enumerate_mut", |b| {
+        b.iter(|| {
+            for (i, item) in black_box(&mut array).enumerate_mut() {
+                *item = item.wrapping_add(i as u64);
+            }
+        })
+    });
+    group.bench_function("iter_mut_enumerate", |b| {
+        b.iter(|| {
+            for (i, item) in black_box(&mut array).iter_mut().enumerate() {
+                *item = item.wrapping_add(i as u64);
+            }
+        })
+    });
+    group.finish();
+}
+
+fn nth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nth");
+    for len in [1 << 10, 1 << 20] {
+        let array = HeapArray::from_exact_iter((0..len).map(|i| i as u64));
+        group.bench_function(format!("{len}"), |b| {
+            b.iter(|| black_box(&array).iter().nth(black_box(len - 1)).copied())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_exact_iter, indexing, fold, enumerate_mut, nth);
+criterion_main!(benches);
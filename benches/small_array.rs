@@ -0,0 +1,19 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use little_collections::{heap_array::HeapArray, small_array::SmallArray};
+use std::hint::black_box;
+
+fn construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("construction");
+    for len in [4, 1024] {
+        group.bench_function(format!("small_array/{len}"), |b| {
+            b.iter(|| SmallArray::<u64, 8>::from_exact_iter((0..black_box(len)).map(|i| i as u64)))
+        });
+        group.bench_function(format!("heap_array/{len}"), |b| {
+            b.iter(|| HeapArray::from_exact_iter((0..black_box(len)).map(|i| i as u64)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, construction);
+criterion_main!(benches);
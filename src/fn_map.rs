@@ -11,8 +11,8 @@ impl<K, V> FnMap<K, V> {
             buckets: Vec::new()
         }
     }
-    pub fn insert(&mut self, key: K, value: V) -> () {
-        if self.buckets.len() == 0 {
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.buckets.is_empty() {
             self.buckets.push(Some(((self.index_fn)(&key), key, value)));
             return;
         }
@@ -32,7 +32,7 @@ impl<K, V> FnMap<K, V> {
         }
     }
     pub fn get(&self, key: &K) -> Option<&V> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let id = (self.index_fn)(key);
         let index = id % self.buckets.len();
         if let Some((pair_id, _, v)) = &self.buckets[index] {
@@ -42,7 +42,7 @@ impl<K, V> FnMap<K, V> {
         else {None}
     }
     pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let id = (self.index_fn)(key);
         let index = id % self.buckets.len();
         if let Some((pair_id, _, v)) = &mut self.buckets[index] {
@@ -52,7 +52,7 @@ impl<K, V> FnMap<K, V> {
         else {None}
     }
     pub fn get_key_mut(&mut self, key: &K) -> Option<KeyMutGuard<'_, K, V>> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let id = (self.index_fn)(key);
         let index = id % self.buckets.len();
         if let Some((pair_id, _, _)) = self.buckets[index] {
@@ -67,12 +67,12 @@ impl<K, V> FnMap<K, V> {
         else {None}
     }
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let index = (self.index_fn)(key) % self.buckets.len();
         self.buckets[index].take().map(|(_, _, v)| v)
     }
     pub fn remove_pair(&mut self, key: &K) -> Option<(K, V)> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let index = (self.index_fn)(key) % self.buckets.len();
         self.buckets[index].take().map(|(_, k, v)| (k, v))
     }
@@ -82,21 +82,19 @@ impl<K, V> FnMap<K, V> {
             pos: 0
         }
     }
-    fn resize(&mut self) -> () {
+    fn resize(&mut self) {
         let new_len = self.buckets.len() * 2;
         let old_buckets = replace(&mut self.buckets, Vec::with_capacity(new_len));
         for _ in 0..new_len {
             self.buckets.push(None);
         }
-        for element in old_buckets {
-            if let Some((index, key, value)) = element {
-                self.buckets[index % new_len] = Some((index, key, value));
-            }
+        for (index, key, value) in old_buckets.into_iter().flatten() {
+            self.buckets[index % new_len] = Some((index, key, value));
         }
     }
 }
 impl<K: Clone, V: Clone> FnMap<K, V> {
-    pub fn clear(&mut self) -> () {
+    pub fn clear(&mut self) {
         self.buckets = vec![None; self.buckets.len()];
     }
 }
@@ -11,8 +11,8 @@ impl<V> FnSet<V> {
             buckets: Vec::new()
         }
     }
-    pub fn insert(&mut self, value: V) -> () {
-        if self.buckets.len() == 0 {
+    pub fn insert(&mut self, value: V) {
+        if self.buckets.is_empty() {
             self.buckets.push(Some(((self.index_fn)(&value), value)));
             return;
         }
@@ -32,7 +32,7 @@ impl<V> FnSet<V> {
         }
     }
     pub fn get(&self, id: usize) -> Option<&V> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let index = id % self.buckets.len();
         if let Some((pair_id, v)) = &self.buckets[index] {
             if *pair_id == id {Some(v)}
@@ -41,7 +41,7 @@ impl<V> FnSet<V> {
         else {None}
     }
     pub fn get_mut(&mut self, id: usize) -> Option<MutGuard<'_, V>> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let index = id % self.buckets.len();
         if let Some((pair_id, _)) = self.buckets[index] {
             if pair_id == id {
@@ -55,7 +55,7 @@ impl<V> FnSet<V> {
         else {None}
     }
     pub fn remove(&mut self, id: usize) -> Option<V> {
-        if self.buckets.len() == 0 {return None;}
+        if self.buckets.is_empty() {return None;}
         let index = id % self.buckets.len();
         self.buckets[index].take().map(|(_, v)| v)
     }
@@ -65,16 +65,14 @@ impl<V> FnSet<V> {
             pos: 0
         }
     }
-    fn resize(&mut self) -> () {
+    fn resize(&mut self) {
         let new_len = self.buckets.len() * 2;
         let old_buckets = replace(&mut self.buckets, Vec::with_capacity(new_len));
         for _ in 0..new_len {
             self.buckets.push(None);
         }
-        for element in old_buckets {
-            if let Some((index, value)) = element {
-                self.buckets[index % new_len] = Some((index, value));
-            }
+        for (index, value) in old_buckets.into_iter().flatten() {
+            self.buckets[index % new_len] = Some((index, value));
         }
     }
 }
@@ -1,13 +1,21 @@
 use std::{
-    iter::{ExactSizeIterator, IntoIterator, Iterator},
-    slice::{from_raw_parts, from_raw_parts_mut},
+    iter::{Cloned, Copied, Enumerate, ExactSizeIterator, IntoIterator, Iterator, Product, Rev, Skip, StepBy, Sum, Zip},
+    slice::{ChunkBy, ChunksExact, ChunksExactMut, ChunksMut, RChunks, RChunksMut, from_raw_parts, from_raw_parts_mut},
     alloc::{Layout, alloc, dealloc},
-    mem::{ManuallyDrop, size_of},
-    ops::{Index, IndexMut},
+    mem::{self, ManuallyDrop, MaybeUninit, align_of, size_of, swap},
+    ops::{Add, Index, IndexMut, Mul, Range, Sub},
     marker::PhantomData,
-    ptr::NonNull,
-    fmt::Debug
+    borrow::Borrow,
+    cmp::Ordering,
+    ptr::{NonNull, copy_nonoverlapping, slice_from_raw_parts_mut, without_provenance_mut},
+    fmt::{Debug, Display, Formatter},
+    error::Error,
+    num::NonZeroUsize,
+    thread::{available_parallelism, scope}
 };
+use crate::shared_array::SharedArray;
+
+const PAR_FILL_MIN_CHUNK: usize = 4096;
 
 #[macro_export]
 macro_rules! array {
@@ -22,7 +30,8 @@ macro_rules! array {
 pub struct HeapArray<T> {
     ptr: NonNull<T>,
     len: usize,
-    _marker: PhantomData<T>
+    _marker: PhantomData<T>,
+    align: usize
 }
 impl<T: Clone> HeapArray<T> {
     #[inline]
@@ -30,27 +39,22 @@ impl<T: Clone> HeapArray<T> {
     pub fn to_vec(&self) -> Vec<T> {
         self.as_slice().to_vec()
     }
+    pub fn clone_into_vec(&self, out: &mut Vec<T>) {
+        out.clear();
+        out.extend_from_slice(self.as_slice());
+    }
+    #[inline]
+    pub fn cloned(&self) -> Cloned<Iter<'_, T>> {
+        self.iter().cloned()
+    }
 }
 impl<T: Clone> Clone for HeapArray<T> {
     fn clone(&self) -> Self {
-        if self.len == 0 {
-            Self {
-                ptr: NonNull::dangling(),
-                len: 0,
-                _marker: PhantomData,
-            }
-        }
-        else {
-            let ptr = unsafe {alloc(Layout::array::<T>(self.len).unwrap())} as *mut T;
-            for i in 0..self.len {
-                unsafe {ptr.add(i).write(self.ptr.add(i).read().clone());}
-            }
-            Self {
-                ptr: unsafe {NonNull::new_unchecked(ptr)},
-                len: self.len,
-                _marker: PhantomData
-            }
+        let mut partial = PartialArray::with_alignment(self.len, self.align);
+        for item in self.as_slice() {
+            partial.push(item.clone());
         }
+        partial.finish()
     }
 }
 impl<T: Clone> HeapArray<T> {
@@ -61,6 +65,7 @@ impl<T: Clone> HeapArray<T> {
                 ptr: NonNull::dangling(),
                 len: 0,
                 _marker: PhantomData,
+                align: align_of::<T>(),
             };
         }
         if size_of::<T>() == 0 {
@@ -68,6 +73,7 @@ impl<T: Clone> HeapArray<T> {
                 ptr: NonNull::dangling(),
                 len,
                 _marker: PhantomData,
+                align: align_of::<T>(),
             };
         }
         let layout = Layout::array::<T>(len)
@@ -85,6 +91,308 @@ impl<T: Clone> HeapArray<T> {
             ptr: unsafe { NonNull::new_unchecked(ptr) },
             len,
             _marker: PhantomData,
+            align: align_of::<T>(),
+        }
+    }
+    pub fn from_slice_exact(slice: &[T], expected_len: usize) -> Result<Self, LenMismatch> {
+        if slice.len() != expected_len {
+            return Err(LenMismatch {expected: expected_len, actual: slice.len()});
+        }
+        Ok(Self::from_slice(slice))
+    }
+}
+impl<T: PartialOrd> HeapArray<T> {
+    #[inline]
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.as_slice().is_sorted()
+    }
+}
+impl<T: Ord> HeapArray<T> {
+    #[inline]
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        self.iter().min()
+    }
+    #[inline]
+    #[must_use]
+    pub fn max(&self) -> Option<&T> {
+        self.iter().max()
+    }
+    #[must_use]
+    pub fn position_min(&self) -> Option<usize> {
+        self.position_by(|item, best| item < best)
+    }
+    #[must_use]
+    pub fn position_max(&self) -> Option<usize> {
+        self.position_by(|item, best| item > best)
+    }
+    fn position_by<F: FnMut(&T, &T) -> bool>(&self, mut better: F) -> Option<usize> {
+        if self.len == 0 {return None;}
+        let mut best = 0;
+        for i in 1..self.len {
+            if better(&self[i], &self[best]) {best = i;}
+        }
+        Some(best)
+    }
+    #[must_use]
+    pub fn sort_dedup(mut self) -> Self {
+        self.as_mut_slice().sort_unstable();
+        let mut kept: Vec<T> = Vec::with_capacity(self.len);
+        for item in self {
+            if kept.last() != Some(&item) {
+                kept.push(item);
+            }
+        }
+        HeapArray::from_boxed_slice(kept.into_boxed_slice())
+    }
+    #[must_use]
+    pub fn insert_sorted(self, value: T) -> Self {
+        let pos = self.partition_point(|item| item <= &value);
+        let mut partial = PartialArray::new(self.len + 1);
+        let mut iter = self.into_iter();
+        for item in iter.by_ref().take(pos) {
+            partial.push(item);
+        }
+        partial.push(value);
+        for item in iter {
+            partial.push(item);
+        }
+        partial.finish()
+    }
+    #[must_use]
+    pub fn argsort(&self) -> HeapArray<usize> {
+        let mut indices = HeapArray::from_exact_iter(0..self.len);
+        indices.sort_by_key(|&i| &self[i]);
+        indices
+    }
+}
+impl<T: Copy> HeapArray<T> {
+    #[inline]
+    pub fn copied(&self) -> Copied<Iter<'_, T>> {
+        self.iter().copied()
+    }
+    pub fn copy_from_slice(&mut self, src: &[T]) -> usize {
+        let count = self.len.min(src.len());
+        unsafe {copy_nonoverlapping(src.as_ptr(), self.ptr.as_ptr(), count);}
+        count
+    }
+    pub fn copy_from_slice_exact(&mut self, src: &[T]) {
+        if src.len() != self.len {
+            panic!("source slice length ({}) does not match HeapArray length ({})", src.len(), self.len);
+        }
+        self.copy_from_slice(src);
+    }
+}
+impl<T: Clone> HeapArray<T> {
+    pub fn clone_from_slice(&mut self, src: &[T]) {
+        if src.len() != self.len {
+            panic!("source slice length ({}) does not match HeapArray length ({})", src.len(), self.len);
+        }
+        for (slot, item) in self.iter_mut().zip(src) {
+            slot.clone_from(item);
+        }
+    }
+    #[must_use]
+    pub fn repeat(&self, n: usize) -> Self {
+        let len = self.len.checked_mul(n).expect("HeapArray::repeat length overflow");
+        let mut partial = PartialArray::new(len);
+        for _ in 0..n {
+            for item in self.iter() {
+                partial.push(item.clone());
+            }
+        }
+        partial.finish()
+    }
+    #[must_use]
+    pub fn rotated_left(&self, n: usize) -> Self {
+        if self.len == 0 {return Self::default();}
+        let mid = n % self.len;
+        HeapArray::from_exact_iter((0..self.len).map(|i| self[(i + mid) % self.len].clone()))
+    }
+    #[must_use]
+    pub fn rotated_right(&self, n: usize) -> Self {
+        if self.len == 0 {return Self::default();}
+        self.rotated_left(self.len - n % self.len)
+    }
+    #[must_use]
+    pub fn grow(self, additional: usize, fill: T) -> Self {
+        match self.try_grow(additional, fill) {
+            Ok(array) => array,
+            Err(err) => panic!("{}", err)
+        }
+    }
+    pub fn try_grow(self, additional: usize, fill: T) -> Result<Self, TryGrowError<T>> {
+        let Some(len) = self.len.checked_add(additional) else {
+            return Err(TryGrowError {array: self, kind: TryGrowErrorKind::CapacityOverflow});
+        };
+        let ptr = match Self::try_allocate(len, self.align) {
+            Ok(ptr) => ptr,
+            Err(kind) => return Err(TryGrowError {array: self, kind})
+        };
+        let this = ManuallyDrop::new(self);
+        let mut partial = PartialArray {
+            ptr,
+            len,
+            align: this.align,
+            init: this.len,
+        };
+        unsafe {
+            copy_nonoverlapping(this.ptr.as_ptr(), ptr.as_ptr(), this.len);
+            Self::deallocate(this.ptr, this.len, this.align);
+        }
+        for _ in 0..additional {
+            partial.push(fill.clone());
+        }
+        Ok(partial.finish())
+    }
+    /// Tiles `pattern` across the whole array, so element `i` becomes `pattern[i % pattern.len()]`.
+    ///
+    /// Panics if `pattern` is empty.
+    pub fn fill_pattern(&mut self, pattern: &[T]) {
+        if pattern.is_empty() {panic!("fill pattern must not be empty");}
+        for (item, value) in self.iter_mut().zip(pattern.iter().cycle()) {
+            *item = value.clone();
+        }
+    }
+}
+impl<T: Clone + Send + Sync> HeapArray<T> {
+    pub fn par_fill(&mut self, value: T) {
+        let threads = available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+            .min(self.len.div_ceil(PAR_FILL_MIN_CHUNK))
+            .max(1);
+        if threads == 1 {
+            self.as_mut_slice().fill(value);
+            return;
+        }
+        let chunk_size = self.len.div_ceil(threads);
+        let value = &value;
+        scope(|s| {
+            for chunk in self.as_mut_slice().chunks_mut(chunk_size) {
+                s.spawn(move || chunk.fill(value.clone()));
+            }
+        });
+    }
+}
+#[cfg(feature = "checksum")]
+impl<T: bytemuck::Pod> HeapArray<T> {
+    #[must_use]
+    pub fn crc32(&self) -> u32 {
+        crc32fast::hash(bytemuck::cast_slice(self.as_slice()))
+    }
+}
+#[cfg(feature = "cast")]
+impl HeapArray<u8> {
+    pub fn as_slice_of<U: bytemuck::Pod>(&self) -> Result<&[U], CastError> {
+        if self.len == 0 {return Ok(&[]);}
+        bytemuck::try_cast_slice(self.as_slice()).map_err(|err| match err {
+            bytemuck::PodCastError::TargetAlignmentGreaterAndInputNotAligned => CastError::Misaligned {
+                addr: self.as_ptr().addr(),
+                align: align_of::<U>(),
+            },
+            _ => CastError::SizeMismatch {len: self.len, size: size_of::<U>()}
+        })
+    }
+}
+#[cfg(feature = "rand")]
+impl<T> HeapArray<T> {
+    #[inline]
+    pub fn shuffle<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        rand::seq::SliceRandom::shuffle(self.as_mut_slice(), rng);
+    }
+    #[inline]
+    #[must_use]
+    pub fn choose<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        rand::seq::IndexedRandom::choose(self.as_slice(), rng)
+    }
+}
+impl<S: Borrow<str>> HeapArray<S> {
+    #[inline]
+    #[must_use]
+    pub fn join(&self, sep: &str) -> String {
+        self.as_slice().join(sep)
+    }
+}
+impl<T: Clone> HeapArray<HeapArray<T>> {
+    #[must_use]
+    pub fn flatten(&self) -> HeapArray<T> {
+        let len = self.iter().map(HeapArray::len).sum();
+        let mut partial = PartialArray::new(len);
+        for inner in self.iter() {
+            for item in inner.iter() {
+                partial.push(item.clone());
+            }
+        }
+        partial.finish()
+    }
+}
+impl HeapArray<u8> {
+    /// Returns a copy of the buffer with every non-overlapping occurrence of `from` replaced by `to`.
+    ///
+    /// An empty `from` matches nothing, so the buffer is returned unchanged.
+    #[must_use]
+    pub fn replace_all(&self, from: &[u8], to: &[u8]) -> HeapArray<u8> {
+        if from.is_empty() {return self.clone();}
+        let bytes = self.as_slice();
+        let mut result = Vec::with_capacity(bytes.len());
+        let mut pos = 0;
+        while pos < bytes.len() {
+            if bytes[pos..].starts_with(from) {
+                result.extend_from_slice(to);
+                pos += from.len();
+            }
+            else {
+                result.push(bytes[pos]);
+                pos += 1;
+            }
+        }
+        HeapArray::from_boxed_slice(result.into_boxed_slice())
+    }
+}
+impl<T: PartialEq> HeapArray<T> {
+    #[must_use]
+    pub fn find_subslice(&self, needle: &[T]) -> Option<usize> {
+        if needle.is_empty() {return Some(0);}
+        if needle.len() > self.len {return None;}
+        self.as_slice().windows(needle.len()).position(|window| window == needle)
+    }
+    #[inline]
+    #[must_use]
+    pub fn contains_subslice(&self, needle: &[T]) -> bool {
+        self.find_subslice(needle).is_some()
+    }
+}
+impl<T: Default> HeapArray<T> {
+    #[must_use]
+    pub fn new_default(len: usize) -> Self {
+        let mut partial = PartialArray::new(len);
+        for _ in 0..len {
+            partial.push(T::default());
+        }
+        partial.finish()
+    }
+    #[inline]
+    pub fn take(&mut self, index: usize) -> T {
+        mem::take(&mut self[index])
+    }
+}
+impl<T> HeapArray<MaybeUninit<T>> {
+    /// Converts the array into a `HeapArray<T>` without copying.
+    ///
+    /// # Safety
+    ///
+    /// Every element must be fully initialized. Calling this when any slot is still
+    /// uninitialized is undefined behavior, exactly as with `MaybeUninit::assume_init`.
+    #[must_use]
+    pub unsafe fn assume_init(self) -> HeapArray<T> {
+        let this = ManuallyDrop::new(self);
+        HeapArray {
+            ptr: this.ptr.cast(),
+            len: this.len,
+            _marker: PhantomData,
+            align: this.align,
         }
     }
 }
@@ -93,32 +401,183 @@ impl<T> Default for HeapArray<T> {
         Self {
             ptr: NonNull::dangling(),
             len: 0,
-            _marker: PhantomData
+            _marker: PhantomData,
+            align: align_of::<T>(),
         }
     }
 }
 impl<T> HeapArray<T> {
     #[must_use]
     pub fn new(value: T, len: usize) -> Self {
-        if len == 0 {
-            Self {
-                ptr: NonNull::dangling(),
-                len: 0,
-                _marker: PhantomData,
+        let ptr = Self::allocate(len, align_of::<T>());
+        let value_ptr = &value as *const T;
+        for i in 0..len {
+            unsafe {ptr.add(i).write(value_ptr.read());}
+        }
+        Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+            align: align_of::<T>(),
+        }
+    }
+    pub fn try_from_iter<E, I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Result<Self, E> {
+        let items = iter.into_iter().collect::<Result<Vec<T>, E>>()?;
+        Ok(Self::from_boxed_slice(items.into_boxed_slice()))
+    }
+    /// Creates an array of `len` clones of `value` whose buffer is aligned to `align` bytes,
+    /// e.g. 64 for cache-line-aligned buffers.
+    ///
+    /// Panics if `align` is not a power of two or is smaller than `align_of::<T>()`.
+    #[must_use]
+    pub fn with_alignment(value: T, len: usize, align: usize) -> Self where T: Clone {
+        Self::check_alignment(align);
+        let mut partial = PartialArray::with_alignment(len, align);
+        for _ in 0..len {
+            partial.push(value.clone());
+        }
+        partial.finish()
+    }
+    /// Like [`HeapArray::from_slice`], but with the buffer aligned to `align` bytes.
+    ///
+    /// Panics if `align` is not a power of two or is smaller than `align_of::<T>()`.
+    #[must_use]
+    pub fn from_slice_aligned(slice: &[T], align: usize) -> Self where T: Clone {
+        Self::check_alignment(align);
+        let mut partial = PartialArray::with_alignment(slice.len(), align);
+        for item in slice {
+            partial.push(item.clone());
+        }
+        partial.finish()
+    }
+    /// Clones `len` elements starting at `ptr` into a new array, after checking that `ptr`
+    /// is aligned for `T`. Meant for buffers handed over through FFI.
+    ///
+    /// # Safety
+    ///
+    /// Apart from alignment, which is checked, `ptr` must meet the requirements of
+    /// `slice::from_raw_parts(ptr, len)`: it must be non-null, valid for reads of `len`
+    /// elements and point to `len` initialized values of `T`.
+    pub unsafe fn try_from_slice_aligned(ptr: *const T, len: usize) -> Result<Self, Misaligned> where T: Clone {
+        if !ptr.is_aligned() {
+            return Err(Misaligned {addr: ptr.addr(), align: align_of::<T>()});
+        }
+        Ok(Self::from_slice(unsafe {from_raw_parts(ptr, len)}))
+    }
+    /// Allocates an array of `len` uninitialized slots, like `Box::new_uninit_slice`.
+    ///
+    /// Every slot must be written before calling [`HeapArray::assume_init`].
+    #[must_use]
+    pub fn new_uninit(len: usize) -> HeapArray<MaybeUninit<T>> {
+        HeapArray {
+            ptr: Self::allocate(len, align_of::<T>()).cast(),
+            len,
+            _marker: PhantomData,
+            align: align_of::<T>(),
+        }
+    }
+    pub fn from_exact_iter<I: ExactSizeIterator<Item = T>>(iter: I) -> Self {
+        let len = iter.len();
+        let mut partial = PartialArray::new(len);
+        for item in iter.take(len) {
+            partial.push(item);
+        }
+        if partial.init != len {
+            panic!("iterator yielded {} elements but reported a length of {}", partial.init, len);
+        }
+        partial.finish()
+    }
+    pub fn concat<I: IntoIterator<Item = HeapArray<T>>>(arrays: I) -> Self {
+        let arrays: Vec<HeapArray<T>> = arrays.into_iter().collect();
+        let len = arrays.iter().map(HeapArray::len).sum();
+        let mut partial = PartialArray::new(len);
+        for array in arrays {
+            for item in array {
+                partial.push(item);
             }
         }
-        else {
-            if size_of::<T>() == 0 {}
-            let array_ptr = unsafe {alloc(Layout::array::<T>(len).unwrap())} as *mut T;
-            let value_ptr = &value as *const T;
-            for i in 0..len {
-                unsafe {array_ptr.add(i).write(value_ptr.read());}
+        partial.finish()
+    }
+    pub fn compact<F: FnMut(&mut T) -> bool>(self, mut pred: F) -> HeapArray<T> {
+        let mut kept = Vec::with_capacity(self.len);
+        for mut item in self {
+            if pred(&mut item) {
+                kept.push(item);
             }
-            Self {
-                ptr: unsafe {NonNull::new_unchecked(array_ptr)},
-                len,
-                _marker: PhantomData
+        }
+        HeapArray::from_boxed_slice(kept.into_boxed_slice())
+    }
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(self, mut key: F) -> HeapArray<T> {
+        let mut last = None;
+        self.compact(|item| {
+            let k = key(item);
+            if last.as_ref() == Some(&k) {false}
+            else {
+                last = Some(k);
+                true
             }
+        })
+    }
+    pub fn try_map<U, E, F: FnMut(T) -> Result<U, E>>(self, mut f: F) -> Result<HeapArray<U>, E> {
+        let mut partial = PartialArray::new(self.len);
+        for item in self {
+            partial.push(f(item)?);
+        }
+        Ok(partial.finish())
+    }
+    #[must_use]
+    pub fn split_off(self, mid: usize) -> (HeapArray<T>, HeapArray<T>) {
+        if mid > self.len {
+            panic!("split index out of bounds: the len is {} but the index is {}", self.len, mid);
+        }
+        let mut iter = self.into_iter();
+        let head = HeapArray::from_exact_iter(iter.by_ref().take(mid));
+        let tail = HeapArray::from_exact_iter(iter);
+        (head, tail)
+    }
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        if self.len == 0 {return Vec::new();}
+        let this = ManuallyDrop::new(self);
+        if this.align == align_of::<T>() {
+            return unsafe {Vec::from_raw_parts(this.ptr.as_ptr(), this.len, this.len)};
+        }
+        let mut vec = Vec::with_capacity(this.len);
+        unsafe {
+            copy_nonoverlapping(this.ptr.as_ptr(), vec.as_mut_ptr(), this.len);
+            vec.set_len(this.len);
+            Self::deallocate(this.ptr, this.len, this.align);
+        }
+        vec
+    }
+    pub(crate) fn into_uninit(self) -> HeapArray<MaybeUninit<T>> {
+        let this = ManuallyDrop::new(self);
+        HeapArray {
+            ptr: this.ptr.cast(),
+            len: this.len,
+            _marker: PhantomData,
+            align: this.align,
+        }
+    }
+    #[must_use]
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        if self.align != align_of::<T>() {
+            return self.into_vec().into_boxed_slice();
+        }
+        let this = ManuallyDrop::new(self);
+        unsafe {Box::from_raw(slice_from_raw_parts_mut(this.ptr.as_ptr(), this.len))}
+    }
+    #[must_use]
+    pub fn into_shared(self) -> SharedArray<T> {
+        SharedArray::from(self)
+    }
+    pub fn try_into_array<const N: usize>(self) -> Result<[T; N], HeapArray<T>> {
+        if self.len != N {return Err(self);}
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let array = this.ptr.cast::<[T; N]>().read();
+            Self::deallocate(this.ptr, this.len, this.align);
+            Ok(array)
         }
     }
     #[inline]
@@ -131,6 +590,35 @@ impl<T> HeapArray<T> {
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         unsafe {from_raw_parts_mut(self.ptr.as_ptr(), self.len)}
     }
+    /// Returns a raw pointer to the array's buffer.
+    ///
+    /// Like `Vec::as_ptr`, the pointer is dangling but well-aligned for empty arrays
+    /// and arrays of zero-sized types.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+    /// Returns a raw mutable pointer to the array's buffer.
+    ///
+    /// Like `Vec::as_mut_ptr`, the pointer is dangling but well-aligned for empty arrays
+    /// and arrays of zero-sized types.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+    #[inline]
+    fn end_ptr(&self) -> *mut T {
+        if size_of::<T>() == 0 {self.ptr.as_ptr().wrapping_byte_add(self.len)}
+        else {unsafe {self.ptr.as_ptr().add(self.len)}}
+    }
+    #[inline]
+    #[must_use]
+    pub fn max_capacity() -> usize {
+        if size_of::<T>() == 0 {usize::MAX}
+        else {isize::MAX as usize / size_of::<T>()}
+    }
     #[inline]
     #[must_use]
     pub fn len(&self) -> usize {
@@ -159,11 +647,150 @@ impl<T> HeapArray<T> {
             None
         }
     }
+    /// Returns a reference to the element at `index` without a bounds check.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `len`. An out-of-bounds index is undefined behavior,
+    /// exactly as with `slice::get_unchecked`.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        debug_assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        unsafe {&*self.ptr.add(index).as_ptr()}
+    }
+    /// Returns a mutable reference to the element at `index` without a bounds check.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `len`. An out-of-bounds index is undefined behavior,
+    /// exactly as with `slice::get_unchecked_mut`.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        debug_assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        unsafe {&mut *self.ptr.add(index).as_ptr()}
+    }
+    /// Swaps the contents of two arrays of equal length in O(1) by exchanging their buffers.
+    ///
+    /// Panics if the lengths differ.
+    #[inline]
+    pub fn swap_with(&mut self, other: &mut HeapArray<T>) {
+        if self.len != other.len {
+            panic!("cannot swap HeapArrays of different lengths: {} and {}", self.len, other.len);
+        }
+        swap(&mut self.ptr, &mut other.ptr);
+        swap(&mut self.align, &mut other.align);
+    }
+    #[inline]
+    #[must_use]
+    pub fn get_clamped(&self, index: usize) -> Option<&T> {
+        if self.len == 0 {return None;}
+        self.get(index.min(self.len - 1))
+    }
+    #[inline]
+    #[must_use]
+    pub fn get_wrapping(&self, index: usize) -> &T {
+        if self.len == 0 {panic!("cannot index an empty HeapArray with wrapping");}
+        &self[index % self.len]
+    }
+    #[inline]
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        let len = self.len;
+        self.get(index).ok_or(IndexError {index, len})
+    }
+    #[inline]
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        let len = self.len;
+        self.get_mut(index).ok_or(IndexError {index, len})
+    }
+    #[inline]
+    #[must_use]
+    pub fn split_at_checked(&self, mid: usize) -> Option<(&[T], &[T])> {
+        self.as_slice().split_at_checked(mid)
+    }
+    #[inline]
+    #[must_use]
+    pub fn split_at_mut_checked(&mut self, mid: usize) -> Option<(&mut [T], &mut [T])> {
+        self.as_mut_slice().split_at_mut_checked(mid)
+    }
+    #[inline]
+    pub fn replace(&mut self, index: usize, value: T) -> T {
+        mem::replace(&mut self[index], value)
+    }
+    #[inline]
+    pub fn try_replace(&mut self, index: usize, value: T) -> Option<T> {
+        self.get_mut(index).map(|slot| mem::replace(slot, value))
+    }
+    pub fn scan<U, F: FnMut(&mut U, &T) -> U>(&self, init: U, mut f: F) -> HeapArray<U> {
+        let mut state = init;
+        HeapArray::from_exact_iter(self.iter().map(|item| f(&mut state, item)))
+    }
+    pub fn zip_with<U, R, F: FnMut(&T, &U) -> R>(&self, other: &HeapArray<U>, mut f: F) -> HeapArray<R> {
+        if self.len != other.len {
+            panic!("cannot zip HeapArrays of different lengths: {} and {}", self.len, other.len);
+        }
+        HeapArray::from_exact_iter(self.iter().zip(other.iter()).map(|(a, b)| f(a, b)))
+    }
+    #[inline]
+    #[must_use]
+    pub fn partition_point<P: FnMut(&T) -> bool>(&self, pred: P) -> usize {
+        self.as_slice().partition_point(pred)
+    }
+    pub fn reverse(&mut self) {
+        for i in 0..self.len / 2 {
+            unsafe {self.ptr.add(i).swap(self.ptr.add(self.len - 1 - i));}
+        }
+    }
+    #[inline]
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+        self.as_mut_slice().sort_by(compare);
+    }
+    #[inline]
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, key: F) {
+        self.as_mut_slice().sort_by_key(key);
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, compare: F) -> bool {
+        self.as_slice().is_sorted_by(compare)
+    }
+    #[inline]
+    pub fn sort_unstable_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, key: F) {
+        self.as_mut_slice().sort_unstable_by_key(key);
+    }
+    #[inline]
+    pub fn fold<U, F: FnMut(U, &T) -> U>(&self, init: U, mut f: F) -> U {
+        let mut acc = init;
+        for i in 0..self.len {
+            acc = f(acc, unsafe {&*self.ptr.add(i).as_ptr()});
+        }
+        acc
+    }
+    #[inline]
+    pub fn reduce<'a, F: FnMut(&'a T, &'a T) -> &'a T>(&'a self, mut f: F) -> Option<&'a T> {
+        if self.len == 0 {return None;}
+        let mut acc = unsafe {&*self.ptr.as_ptr()};
+        for i in 1..self.len {
+            acc = f(acc, unsafe {&*self.ptr.add(i).as_ptr()});
+        }
+        Some(acc)
+    }
+    #[inline]
+    #[must_use]
+    pub fn sum<'a, S: Sum<&'a T>>(&'a self) -> S {
+        self.iter().sum()
+    }
+    #[inline]
+    #[must_use]
+    pub fn product<'a, P: Product<&'a T>>(&'a self) -> P {
+        self.iter().product()
+    }
     #[inline]
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             ptr: self.ptr.as_ptr(),
-            end: unsafe { self.ptr.as_ptr().add(self.len) },
+            end: self.end_ptr(),
             _marker: PhantomData,
         }
     }
@@ -172,38 +799,220 @@ impl<T> HeapArray<T> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             ptr: self.ptr.as_ptr(),
-            end: unsafe { self.ptr.as_ptr().add(self.len) },
+            end: self.end_ptr(),
             _marker: PhantomData,
         }
     }
-}
-impl<T> Index<usize> for HeapArray<T> {
-    type Output = T;
     #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        if let Some(v) = self.get(index) {v}
-        else {panic!("index out of bounds: the len is {} but the index is {}", self.len, index);}
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+        if chunk_size == 0 {panic!("chunk size must be non-zero");}
+        self.as_mut_slice().chunks_mut(chunk_size)
     }
-}
-impl<T> IndexMut<usize> for HeapArray<T> {
     #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let len = self.len;
-        if let Some(v) = self.get_mut(index) {v}
-        else {panic!("index out of bounds: the len is {} but the index is {}", len, index);}
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
+        if chunk_size == 0 {panic!("chunk size must be non-zero");}
+        self.as_slice().chunks_exact(chunk_size)
     }
-}
-impl<T> Drop for HeapArray<T> {
-    fn drop(&mut self) {
-        if self.len != 0 {
+    #[inline]
+    pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> ChunksExactMut<'_, T> {
+        if chunk_size == 0 {panic!("chunk size must be non-zero");}
+        self.as_mut_slice().chunks_exact_mut(chunk_size)
+    }
+    #[inline]
+    pub fn chunk_by<F: FnMut(&T, &T) -> bool>(&self, eq: F) -> ChunkBy<'_, T, F> {
+        self.as_slice().chunk_by(eq)
+    }
+    #[inline]
+    pub fn rchunks(&self, chunk_size: usize) -> RChunks<'_, T> {
+        if chunk_size == 0 {panic!("chunk size must be non-zero");}
+        self.as_slice().rchunks(chunk_size)
+    }
+    #[inline]
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> RChunksMut<'_, T> {
+        if chunk_size == 0 {panic!("chunk size must be non-zero");}
+        self.as_mut_slice().rchunks_mut(chunk_size)
+    }
+    pub fn for_each_pair_mut<F: FnMut(&mut T, &mut T)>(&mut self, mut f: F) {
+        for pair in self.as_mut_slice().chunks_exact_mut(2) {
+            let (a, b) = pair.split_at_mut(1);
+            f(&mut a[0], &mut b[0]);
+        }
+    }
+    #[inline]
+    pub fn into_indexed_iter(self) -> Enumerate<IntoIter<T>> {
+        self.into_iter().enumerate()
+    }
+    #[inline]
+    pub fn enumerate_mut(&mut self) -> EnumerateMut<'_, T> {
+        EnumerateMut {
+            ptr: self.ptr.as_ptr(),
+            index: 0,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn stride_iter(&self, step: usize) -> StepBy<Iter<'_, T>> {
+        if step == 0 {panic!("stride step must be non-zero");}
+        self.iter().step_by(step)
+    }
+    #[inline]
+    pub fn stride_iter_mut(&mut self, step: usize) -> StepBy<IterMut<'_, T>> {
+        if step == 0 {panic!("stride step must be non-zero");}
+        self.iter_mut().step_by(step)
+    }
+    #[inline]
+    pub fn iter_with_prev(&self) -> WithPrev<'_, T> {
+        WithPrev {
+            iter: self.iter(),
+            prev: None,
+        }
+    }
+    #[inline]
+    pub fn peek_iter(&self) -> PeekIter<'_, T> {
+        PeekIter {
+            ptr: self.ptr.as_ptr(),
+            end: self.end_ptr(),
+            _marker: PhantomData,
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        self.as_slice().as_chunks::<N>()
+    }
+    #[inline]
+    #[must_use]
+    pub fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        self.as_mut_slice().as_chunks_mut::<N>()
+    }
+    #[inline]
+    pub fn array_chunks<const N: usize>(&self) -> ArrayChunks<'_, T, N> {
+        let (chunks, rem) = self.as_chunks::<N>();
+        ArrayChunks {
+            iter: chunks.iter(),
+            rem,
+        }
+    }
+    #[inline]
+    pub fn riter(&self) -> RevIter<'_, T> {
+        RevIter {
+            ptr: self.ptr.as_ptr(),
+            end: self.end_ptr(),
+            _marker: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn pairwise(&self) -> Zip<Iter<'_, T>, Skip<Iter<'_, T>>> {
+        self.iter().zip(self.iter().skip(1))
+    }
+    #[inline]
+    pub fn rev_enumerate(&self) -> Zip<Rev<Range<usize>>, RevIter<'_, T>> {
+        (0..self.len).rev().zip(self.riter())
+    }
+    #[inline]
+    pub fn riter_mut(&mut self) -> RevIterMut<'_, T> {
+        RevIterMut {
+            ptr: self.ptr.as_ptr(),
+            end: self.end_ptr(),
+            _marker: PhantomData,
+        }
+    }
+}
+impl<T> HeapArray<T> {
+    fn allocate(len: usize, align: usize) -> NonNull<T> {
+        match Self::try_allocate(len, align) {
+            Ok(ptr) => ptr,
+            Err(TryGrowErrorKind::CapacityOverflow) => panic!("array layout too large"),
+            Err(TryGrowErrorKind::AllocError) => panic!("Failed to allocate memory for HeapArray")
+        }
+    }
+    fn try_allocate(len: usize, align: usize) -> Result<NonNull<T>, TryGrowErrorKind> {
+        if len == 0 || size_of::<T>() == 0 {
+            return Ok(unsafe {NonNull::new_unchecked(without_provenance_mut(align))});
+        }
+        let layout = Self::layout(len, align)
+            .ok_or(TryGrowErrorKind::CapacityOverflow)?;
+        let ptr = unsafe { alloc(layout) } as *mut T;
+        NonNull::new(ptr).ok_or(TryGrowErrorKind::AllocError)
+    }
+    fn layout(len: usize, align: usize) -> Option<Layout> {
+        Layout::array::<T>(len).ok()?.align_to(align).ok()
+    }
+    fn check_alignment(align: usize) {
+        if !align.is_power_of_two() {
+            panic!("alignment must be a power of two, got {}", align);
+        }
+        if align < align_of::<T>() {
+            panic!("alignment {} is smaller than the alignment of the element type ({})", align, align_of::<T>());
+        }
+    }
+    pub(crate) fn from_boxed_slice(slice: Box<[T]>) -> Self {
+        let len = slice.len();
+        let ptr = Box::into_raw(slice) as *mut T;
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            len,
+            _marker: PhantomData,
+            align: align_of::<T>(),
+        }
+    }
+    unsafe fn deallocate(ptr: NonNull<T>, len: usize, align: usize) {
+        if len == 0 || size_of::<T>() == 0 {return;}
+        unsafe {
+            dealloc(
+                ptr.as_ptr() as *mut u8,
+                Self::layout(len, align).unwrap(),
+            );
+        }
+    }
+}
+impl<T> Index<usize> for HeapArray<T> {
+    type Output = T;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        if let Some(v) = self.get(index) {v}
+        else {panic!("index out of bounds: the len is {} but the index is {}", self.len, index);}
+    }
+}
+impl<T> IndexMut<usize> for HeapArray<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        if let Some(v) = self.get_mut(index) {v}
+        else {panic!("index out of bounds: the len is {} but the index is {}", len, index);}
+    }
+}
+macro_rules! impl_elementwise_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl<T: $trait<Output = T> + Copy> $trait for &HeapArray<T> {
+            type Output = HeapArray<T>;
+            fn $method(self, rhs: Self) -> Self::Output {
+                if self.len != rhs.len {
+                    panic!("cannot {} HeapArrays of different lengths: {} and {}", stringify!($method), self.len, rhs.len);
+                }
+                HeapArray::from_exact_iter(self.iter().zip(rhs.iter()).map(|(a, b)| *a $op *b))
+            }
+        }
+    };
+}
+impl_elementwise_op!(Add, add, +);
+impl_elementwise_op!(Sub, sub, -);
+impl_elementwise_op!(Mul, mul, *);
+impl<T: Mul<Output = T> + Copy> Mul<T> for &HeapArray<T> {
+    type Output = HeapArray<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        HeapArray::from_exact_iter(self.iter().map(|a| *a * rhs))
+    }
+}
+impl<T> Drop for HeapArray<T> {
+    fn drop(&mut self) {
+        if self.len != 0 {
             unsafe {
                 for i in 0..self.len {
                     self.ptr.add(i).drop_in_place();
                 }
-                dealloc(
-                    self.ptr.as_ptr() as *mut u8,
-                    Layout::array::<T>(self.len).unwrap(),
-                );
+                Self::deallocate(self.ptr, self.len, self.align);
             }
         }
     }
@@ -239,6 +1048,16 @@ impl<T: Clone> FromIterator<T> for HeapArray<T> {
         Self::from_slice(&Vec::from_iter(iter))
     }
 }
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for HeapArray<T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<T: PartialEq, const N: usize> PartialEq<HeapArray<T>> for [T; N] {
+    fn eq(&self, other: &HeapArray<T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
 impl<T: Debug> Debug for HeapArray<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.as_slice())
@@ -247,6 +1066,149 @@ impl<T: Debug> Debug for HeapArray<T> {
 unsafe impl<T: Send> Send for HeapArray<T> {}
 unsafe impl<T: Sync> Sync for HeapArray<T> {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryGrowErrorKind {
+    CapacityOverflow,
+    AllocError,
+}
+
+pub struct TryGrowError<T> {
+    array: HeapArray<T>,
+    kind: TryGrowErrorKind,
+}
+impl<T> TryGrowError<T> {
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> TryGrowErrorKind {
+        self.kind
+    }
+    #[inline]
+    #[must_use]
+    pub fn into_array(self) -> HeapArray<T> {
+        self.array
+    }
+}
+impl<T> Debug for TryGrowError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TryGrowError")
+            .field("len", &self.array.len)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+impl<T> Display for TryGrowError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            TryGrowErrorKind::CapacityOverflow => write!(f, "capacity overflow while growing a HeapArray of length {}", self.array.len),
+            TryGrowErrorKind::AllocError => write!(f, "memory allocation failed while growing a HeapArray of length {}", self.array.len),
+        }
+    }
+}
+impl<T> Error for TryGrowError<T> {}
+
+pub struct HeapArrayBuilder<T> {
+    items: Vec<T>,
+}
+impl<T> HeapArrayBuilder<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new()
+        }
+    }
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity)
+        }
+    }
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.items.push(value);
+    }
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    #[must_use]
+    pub fn build(self) -> HeapArray<T> {
+        HeapArray::from_boxed_slice(self.items.into_boxed_slice())
+    }
+}
+impl<T> Default for HeapArrayBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize,
+}
+impl Display for IndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "index out of bounds: the len is {} but the index is {}", self.len, self.index)
+    }
+}
+impl Error for IndexError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Misaligned {
+    pub addr: usize,
+    pub align: usize,
+}
+impl Display for Misaligned {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "address {:#x} is not aligned to {} bytes", self.addr, self.align)
+    }
+}
+impl Error for Misaligned {}
+
+#[cfg(feature = "cast")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastError {
+    SizeMismatch {len: usize, size: usize},
+    Misaligned {addr: usize, align: usize},
+}
+#[cfg(feature = "cast")]
+impl Display for CastError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SizeMismatch {len, size} => write!(f, "byte length {} is not a multiple of the element size {}", len, size),
+            Self::Misaligned {addr, align} => write!(f, "address {:#x} is not aligned to {} bytes", addr, align),
+        }
+    }
+}
+#[cfg(feature = "cast")]
+impl Error for CastError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenMismatch {
+    pub expected: usize,
+    pub actual: usize,
+}
+impl Display for LenMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "length mismatch: expected {} but got {}", self.expected, self.actual)
+    }
+}
+impl Error for LenMismatch {}
+
+/// Number of elements between `ptr` and `end`; for zero-sized types `end` is offset
+/// from `ptr` by the element count in bytes.
+#[inline]
+unsafe fn distance<T>(ptr: *const T, end: *const T) -> usize {
+    if size_of::<T>() == 0 {end.addr() - ptr.addr()}
+    else {unsafe {end.offset_from(ptr) as usize}}
+}
+
 pub struct Iter<'a, T> {
     ptr: *const T,
     end: *const T,
@@ -260,15 +1222,32 @@ impl<'a, T> Iterator for Iter<'a, T> {
             None
         } else {
             let old = self.ptr;
-            self.ptr = unsafe {self.ptr.add(1)};
+            if size_of::<T>() == 0 {self.end = self.end.wrapping_byte_sub(1);}
+            else {self.ptr = unsafe {self.ptr.add(1)};}
             Some(unsafe { &*old })
         }
     }
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = unsafe {self.end.offset_from(self.ptr)} as usize;
+        let len = unsafe {distance(self.ptr, self.end)};
         (len, Some(len))
     }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.ptr = self.end;
+            return None;
+        }
+        if size_of::<T>() == 0 {self.end = self.end.wrapping_byte_sub(n);}
+        else {self.ptr = unsafe {self.ptr.add(n)};}
+        self.next()
+    }
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        let len = self.len();
+        if len == 0 {return None;}
+        self.nth(len - 1)
+    }
 }
 impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
 
@@ -285,18 +1264,203 @@ impl<'a, T> Iterator for IterMut<'a, T> {
             None
         } else {
             let old = self.ptr;
-            self.ptr = unsafe {self.ptr.add(1)};
+            if size_of::<T>() == 0 {self.end = self.end.wrapping_byte_sub(1);}
+            else {self.ptr = unsafe {self.ptr.add(1)};}
             Some(unsafe { &mut *old })
         }
     }
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = unsafe {self.end.offset_from(self.ptr)} as usize;
+        let len = unsafe {distance(self.ptr, self.end)};
         (len, Some(len))
     }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.ptr = self.end;
+            return None;
+        }
+        if size_of::<T>() == 0 {self.end = self.end.wrapping_byte_sub(n);}
+        else {self.ptr = unsafe {self.ptr.add(n)};}
+        self.next()
+    }
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        let len = self.len();
+        if len == 0 {return None;}
+        self.nth(len - 1)
+    }
 }
 impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
 
+pub struct EnumerateMut<'a, T> {
+    ptr: *mut T,
+    index: usize,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+impl<'a, T> Iterator for EnumerateMut<'a, T> {
+    type Item = (usize, &'a mut T);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            None
+        } else {
+            let index = self.index;
+            self.index += 1;
+            Some((index, unsafe { &mut *self.ptr.add(index) }))
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+impl<'a, T> ExactSizeIterator for EnumerateMut<'a, T> {}
+
+pub struct WithPrev<'a, T> {
+    iter: Iter<'a, T>,
+    prev: Option<&'a T>,
+}
+impl<'a, T> Iterator for WithPrev<'a, T> {
+    type Item = (Option<&'a T>, &'a T);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some((self.prev.replace(item), item))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+impl<'a, T> ExactSizeIterator for WithPrev<'a, T> {}
+
+pub struct PeekIter<'a, T> {
+    ptr: *const T,
+    end: *const T,
+    _marker: PhantomData<&'a T>,
+}
+impl<'a, T> PeekIter<'a, T> {
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<&'a T> {
+        self.peek_nth(0)
+    }
+    #[inline]
+    #[must_use]
+    pub fn peek_nth(&self, n: usize) -> Option<&'a T> {
+        if n < self.len() {
+            Some(unsafe { &*self.ptr.add(n) })
+        } else {
+            None
+        }
+    }
+}
+impl<'a, T> Iterator for PeekIter<'a, T> {
+    type Item = &'a T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr == self.end {
+            None
+        } else {
+            let old = self.ptr;
+            if size_of::<T>() == 0 {self.end = self.end.wrapping_byte_sub(1);}
+            else {self.ptr = unsafe {self.ptr.add(1)};}
+            Some(unsafe { &*old })
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = unsafe {distance(self.ptr, self.end)};
+        (len, Some(len))
+    }
+}
+impl<'a, T> ExactSizeIterator for PeekIter<'a, T> {}
+
+pub struct ArrayChunks<'a, T, const N: usize> {
+    iter: std::slice::Iter<'a, [T; N]>,
+    rem: &'a [T],
+}
+impl<'a, T, const N: usize> ArrayChunks<'a, T, N> {
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> &'a [T] {
+        self.rem
+    }
+}
+impl<'a, T, const N: usize> Iterator for ArrayChunks<'a, T, N> {
+    type Item = &'a [T; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayChunks<'a, T, N> {}
+
+pub struct RevIter<'a, T> {
+    ptr: *const T,
+    end: *const T,
+    _marker: PhantomData<&'a T>,
+}
+impl<'a, T> Iterator for RevIter<'a, T> {
+    type Item = &'a T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr == self.end {
+            None
+        } else {
+            if size_of::<T>() == 0 {
+                self.end = self.end.wrapping_byte_sub(1);
+                Some(unsafe { &*self.ptr })
+            } else {
+                self.end = unsafe {self.end.sub(1)};
+                Some(unsafe { &*self.end })
+            }
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = unsafe {distance(self.ptr, self.end)};
+        (len, Some(len))
+    }
+}
+impl<'a, T> ExactSizeIterator for RevIter<'a, T> {}
+
+pub struct RevIterMut<'a, T> {
+    ptr: *mut T,
+    end: *mut T,
+    _marker: PhantomData<&'a mut T>,
+}
+impl<'a, T> Iterator for RevIterMut<'a, T> {
+    type Item = &'a mut T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr == self.end {
+            None
+        } else {
+            if size_of::<T>() == 0 {
+                self.end = self.end.wrapping_byte_sub(1);
+                Some(unsafe { &mut *self.ptr })
+            } else {
+                self.end = unsafe {self.end.sub(1)};
+                Some(unsafe { &mut *self.end })
+            }
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = unsafe {distance(self.ptr, self.end)};
+        (len, Some(len))
+    }
+}
+impl<'a, T> ExactSizeIterator for RevIterMut<'a, T> {}
+
 pub struct IntoIter<T> {
     buf: ManuallyDrop<HeapArray<T>>,
     start: usize,
@@ -319,10 +1483,1219 @@ impl<T> Iterator for IntoIter<T> {
         let len = self.end - self.start;
         (len, Some(len))
     }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.end - self.start);
+        let skipped = slice_from_raw_parts_mut(unsafe {self.buf.ptr.add(self.start).as_ptr()}, skip);
+        self.start += skip;
+        unsafe {skipped.drop_in_place();}
+        self.next()
+    }
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(unsafe {self.buf.ptr.add(self.end).as_ptr().read()})
+        }
+    }
 }
 impl<T> ExactSizeIterator for IntoIter<T> {}
 impl<T> Drop for IntoIter<T> {
     fn drop(&mut self) {
-        for _ in self.by_ref() {}
+        struct DeallocGuard<T> {
+            ptr: NonNull<T>,
+            len: usize,
+            align: usize,
+        }
+        impl<T> Drop for DeallocGuard<T> {
+            fn drop(&mut self) {
+                unsafe {HeapArray::deallocate(self.ptr, self.len, self.align);}
+            }
+        }
+        let _guard = DeallocGuard {
+            ptr: self.buf.ptr,
+            len: self.buf.len,
+            align: self.buf.align,
+        };
+        let remaining = slice_from_raw_parts_mut(unsafe {self.buf.ptr.add(self.start).as_ptr()}, self.end - self.start);
+        self.start = self.end;
+        unsafe {remaining.drop_in_place();}
+    }
+}
+
+struct PartialArray<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    align: usize,
+    init: usize,
+}
+impl<T> PartialArray<T> {
+    fn new(len: usize) -> Self {
+        Self::with_alignment(len, align_of::<T>())
+    }
+    fn with_alignment(len: usize, align: usize) -> Self {
+        Self {
+            ptr: HeapArray::allocate(len, align),
+            len,
+            align,
+            init: 0,
+        }
+    }
+    #[inline]
+    fn push(&mut self, value: T) {
+        debug_assert!(self.init < self.len);
+        unsafe {self.ptr.add(self.init).write(value);}
+        self.init += 1;
+    }
+    fn finish(self) -> HeapArray<T> {
+        debug_assert!(self.init == self.len);
+        let partial = ManuallyDrop::new(self);
+        HeapArray {
+            ptr: partial.ptr,
+            len: partial.len,
+            _marker: PhantomData,
+            align: partial.align,
+        }
+    }
+}
+impl<T> Drop for PartialArray<T> {
+    fn drop(&mut self) {
+        unsafe {
+            for i in 0..self.init {
+                self.ptr.add(i).drop_in_place();
+            }
+            HeapArray::deallocate(self.ptr, self.len, self.align);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::DropCounter;
+    use std::cell::Cell;
+
+    #[test]
+    fn as_ptr_matches_as_slice() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.as_ptr(), array.as_slice().as_ptr());
+        let ptr = array.as_mut_slice().as_mut_ptr();
+        assert_eq!(array.as_mut_ptr(), ptr);
+    }
+    #[test]
+    fn as_ptr_is_aligned_for_empty_and_zst_arrays() {
+        let empty = HeapArray::<u64>::from_slice(&[]);
+        assert!(!empty.as_ptr().is_null());
+        assert!(empty.as_ptr().is_aligned());
+        let zst = HeapArray::new((), 3);
+        assert!(!zst.as_ptr().is_null());
+        assert!(zst.as_ptr().is_aligned());
+    }
+    #[test]
+    fn find_subslice_handles_overlap_and_misses() {
+        let array = HeapArray::from_slice(b"aaab");
+        assert_eq!(array.find_subslice(b"aab"), Some(1));
+        assert_eq!(array.find_subslice(b"aa"), Some(0));
+        assert_eq!(array.find_subslice(b"abc"), None);
+        assert!(array.contains_subslice(b"ab"));
+        assert!(!array.contains_subslice(b"ba"));
+    }
+    #[test]
+    fn find_subslice_edge_cases() {
+        let array = HeapArray::from_slice(&[1, 2]);
+        assert_eq!(array.find_subslice(&[]), Some(0));
+        assert_eq!(array.find_subslice(&[1, 2, 3]), None);
+        assert_eq!(array.find_subslice(&[1, 2]), Some(0));
+    }
+    #[test]
+    fn riter_yields_reverse_order() {
+        let array = HeapArray::from_slice(&[1, 2, 3, 4]);
+        let iter = array.riter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.copied().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    }
+    #[test]
+    fn riter_mut_yields_reverse_order() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.riter_mut().len(), 3);
+        for (next, item) in (10..).zip(array.riter_mut()) {
+            *item = next;
+        }
+        assert_eq!(array.as_slice(), &[12, 11, 10]);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).riter().count(), 0);
+    }
+    #[test]
+    fn copy_from_slice_copies_the_overlap() {
+        let mut array = HeapArray::from_slice(&[0; 3]);
+        assert_eq!(array.copy_from_slice(&[1, 2]), 2);
+        assert_eq!(array.as_slice(), &[1, 2, 0]);
+        assert_eq!(array.copy_from_slice(&[4, 5, 6]), 3);
+        assert_eq!(array.as_slice(), &[4, 5, 6]);
+        assert_eq!(array.copy_from_slice(&[7, 8, 9, 10]), 3);
+        assert_eq!(array.as_slice(), &[7, 8, 9]);
+    }
+    #[test]
+    fn copy_from_slice_exact_accepts_equal_length() {
+        let mut array = HeapArray::from_slice(&[0; 2]);
+        array.copy_from_slice_exact(&[1, 2]);
+        assert_eq!(array.as_slice(), &[1, 2]);
+    }
+    #[test]
+    #[should_panic(expected = "does not match HeapArray length")]
+    fn copy_from_slice_exact_panics_on_mismatch() {
+        HeapArray::from_slice(&[0; 2]).copy_from_slice_exact(&[1]);
+    }
+    struct LyingIter<I> {
+        inner: I,
+        claimed: usize
+    }
+    impl<I: Iterator> Iterator for LyingIter<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<I::Item> {
+            self.inner.next()
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.claimed, Some(self.claimed))
+        }
+    }
+    impl<I: Iterator> ExactSizeIterator for LyingIter<I> {}
+    #[test]
+    fn from_exact_iter_matches_from_iter() {
+        let array = HeapArray::from_exact_iter((0..100).map(|i| i * 2));
+        let collected: HeapArray<i32> = (0..100).map(|i| i * 2).collect();
+        assert_eq!(array.as_slice(), collected.as_slice());
+        assert!(HeapArray::from_exact_iter(std::iter::empty::<String>()).is_empty());
+        assert_eq!(HeapArray::from_exact_iter(std::iter::repeat_n((), 5)).len(), 5);
+    }
+    #[test]
+    fn from_exact_iter_ignores_extra_items() {
+        let array = HeapArray::from_exact_iter(LyingIter {inner: 0..10, claimed: 3});
+        assert_eq!(array.as_slice(), &[0, 1, 2]);
+    }
+    #[test]
+    fn from_exact_iter_drops_written_items_when_short() {
+        let drops = Cell::new(0);
+        let iter = LyingIter {inner: (0..2).map(|i| DropCounter::new(i, &drops)), claimed: 5};
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| HeapArray::from_exact_iter(iter)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+    }
+    #[test]
+    fn chunks_mut_yields_disjoint_chunks() {
+        let mut array = HeapArray::from_exact_iter(0..10);
+        assert_eq!(array.chunks_mut(4).map(|chunk| chunk.len()).collect::<Vec<_>>(), [4, 4, 2]);
+        scope(|s| {
+            for (i, chunk) in array.chunks_mut(4).enumerate() {
+                s.spawn(move || {
+                    for item in chunk {
+                        *item += 100 * i as i32;
+                    }
+                });
+            }
+        });
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 104, 105, 106, 107, 208, 209]);
+    }
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_mut_panics_on_zero() {
+        let _ = HeapArray::from_slice(&[1]).chunks_mut(0);
+    }
+    #[test]
+    fn try_get_reports_index_and_len() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.try_get(1), Ok(&2));
+        *array.try_get_mut(2).unwrap() = 7;
+        assert_eq!(array.as_slice(), &[1, 2, 7]);
+        assert_eq!(array.try_get(5), Err(IndexError {index: 5, len: 3}));
+        let err = array.try_get_mut(3).unwrap_err();
+        assert_eq!((err.index, err.len), (3, 3));
+        assert_eq!(err.to_string(), "index out of bounds: the len is 3 but the index is 3");
+    }
+    #[test]
+    fn new_default_fills_with_defaults() {
+        let strings = HeapArray::<String>::new_default(4);
+        assert_eq!(strings.len(), 4);
+        assert!(strings.iter().all(String::is_empty));
+        assert!(HeapArray::<String>::new_default(0).is_empty());
+        assert_eq!(HeapArray::<()>::new_default(3).len(), 3);
+    }
+    #[test]
+    fn swap_with_double_buffers() {
+        let mut front = HeapArray::from_slice(&[0; 4]);
+        let mut back = HeapArray::new_default(4);
+        for step in 1..=5 {
+            for (i, item) in back.iter_mut().enumerate() {
+                *item = front[i] + step;
+            }
+            let back_ptr = back.as_ptr();
+            front.swap_with(&mut back);
+            assert_eq!(front.as_ptr(), back_ptr);
+        }
+        assert_eq!(front.as_slice(), &[15; 4]);
+        assert_eq!(back.as_slice(), &[10; 4]);
+    }
+    #[test]
+    #[should_panic(expected = "cannot swap HeapArrays of different lengths")]
+    fn swap_with_panics_on_length_mismatch() {
+        HeapArray::from_slice(&[1]).swap_with(&mut HeapArray::from_slice(&[1, 2]));
+    }
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Unit;
+    impl Add for Unit {
+        type Output = Unit;
+        fn add(self, _: Unit) -> Unit {
+            Unit
+        }
     }
-}
\ No newline at end of file
+    #[test]
+    fn elementwise_ops_on_floats() {
+        let a = HeapArray::from_slice(&[1.0, 2.0, 3.0]);
+        let b = HeapArray::from_slice(&[0.5, 0.25, 4.0]);
+        assert_eq!((&a + &b).as_slice(), &[1.5, 2.25, 7.0]);
+        assert_eq!((&a - &b).as_slice(), &[0.5, 1.75, -1.0]);
+        assert_eq!((&a * &b).as_slice(), &[0.5, 0.5, 12.0]);
+        assert_eq!((&a * 2.0).as_slice(), &[2.0, 4.0, 6.0]);
+    }
+    #[test]
+    fn elementwise_ops_on_zero_sized_elements() {
+        let a = HeapArray::from_slice(&[Unit; 3]);
+        assert_eq!((&a + &a).len(), 3);
+    }
+    #[test]
+    #[should_panic(expected = "cannot add HeapArrays of different lengths: 2 and 3")]
+    fn elementwise_ops_panic_on_length_mismatch() {
+        let _ = &HeapArray::from_slice(&[1.0, 2.0]) + &HeapArray::from_slice(&[1.0, 2.0, 3.0]);
+    }
+    #[test]
+    fn reductions_handle_empty_and_single() {
+        let array = HeapArray::from_slice(&[3, 1, 4, 1, 5]);
+        assert_eq!(array.sum::<i32>(), 14);
+        assert_eq!(array.product::<i32>(), 60);
+        assert_eq!((array.min(), array.max()), (Some(&1), Some(&5)));
+        let empty = HeapArray::<i32>::from_slice(&[]);
+        assert_eq!((empty.sum::<i32>(), empty.product::<i32>()), (0, 1));
+        assert_eq!((empty.min(), empty.max()), (None, None));
+        let single = HeapArray::from_slice(&[7]);
+        assert_eq!((single.min(), single.max()), (Some(&7), Some(&7)));
+        assert_eq!(single.sum::<i32>(), 7);
+    }
+    #[test]
+    fn repeat_tiles_contents() {
+        let array = HeapArray::from_slice(&[1, 2]);
+        assert_eq!(array.repeat(3).as_slice(), &[1, 2, 1, 2, 1, 2]);
+        assert!(array.repeat(0).is_empty());
+        assert!(HeapArray::<i32>::from_slice(&[]).repeat(5).is_empty());
+    }
+    #[test]
+    #[should_panic(expected = "HeapArray::repeat length overflow")]
+    fn repeat_panics_on_length_overflow() {
+        let _ = HeapArray::from_slice(&[1, 2]).repeat(usize::MAX);
+    }
+    #[test]
+    fn join_and_flatten() {
+        let words = HeapArray::from_slice(&["a".to_string(), "bc".to_string(), "d".to_string()]);
+        assert_eq!(words.join(","), "a,bc,d");
+        assert_eq!(HeapArray::<String>::from_slice(&[]).join(","), "");
+        let nested = HeapArray::from_slice(&[
+            HeapArray::from_slice(&[1, 2]),
+            HeapArray::from_slice(&[]),
+            HeapArray::from_slice(&[3])
+        ]);
+        assert_eq!(nested.flatten().as_slice(), &[1, 2, 3]);
+    }
+    #[test]
+    fn fill_pattern_wraps() {
+        let mut array = HeapArray::from_slice(&[0; 7]);
+        array.fill_pattern(&[1, 2, 3]);
+        assert_eq!(array.as_slice(), &[1, 2, 3, 1, 2, 3, 1]);
+    }
+    #[test]
+    #[should_panic(expected = "fill pattern must not be empty")]
+    fn fill_pattern_panics_on_empty_pattern() {
+        HeapArray::from_slice(&[0; 2]).fill_pattern(&[]);
+    }
+    #[test]
+    fn from_slice_exact_checks_length() {
+        assert_eq!(HeapArray::from_slice_exact(&[1, 2], 2).unwrap().as_slice(), &[1, 2]);
+        let err = HeapArray::from_slice_exact(&[1, 2], 3).unwrap_err();
+        assert_eq!(err, LenMismatch {expected: 3, actual: 2});
+        assert_eq!(err.to_string(), "length mismatch: expected 3 but got 2");
+    }
+    #[test]
+    fn try_into_array_moves_on_matching_length() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops)));
+        let moved: [DropCounter; 3] = array.try_into_array().unwrap();
+        assert_eq!(drops.get(), 0);
+        assert_eq!(moved.each_ref().map(|item| item.value), [0, 1, 2]);
+        drop(moved);
+        assert_eq!(drops.get(), 3);
+    }
+    #[test]
+    fn try_into_array_returns_array_on_mismatch() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let ptr = array.as_ptr();
+        let back = array.try_into_array::<3>().unwrap_err();
+        assert_eq!(back.as_ptr(), ptr);
+        assert_eq!(back.as_slice(), &["a", "b"]);
+        assert_eq!(HeapArray::<()>::new_default(2).try_into_array::<2>().ok(), Some([(); 2]));
+    }
+    #[test]
+    fn replace_all_changes_length() {
+        let bytes = HeapArray::from_slice(b"a-b-c");
+        assert_eq!(bytes.replace_all(b"-", b"--").as_slice(), b"a--b--c");
+        assert_eq!(bytes.replace_all(b"-b-", b"").as_slice(), b"ac");
+        assert_eq!(HeapArray::from_slice(b"aaa").replace_all(b"aa", b"b").as_slice(), b"ba");
+        assert_eq!(bytes.replace_all(b"x", b"y").as_slice(), b"a-b-c");
+        assert_eq!(bytes.replace_all(b"", b"y").as_slice(), b"a-b-c");
+    }
+    #[test]
+    fn array_chunks_exposes_remainder() {
+        let array = HeapArray::from_exact_iter(0..10);
+        let mut chunks = array.array_chunks::<3>();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.remainder(), &[9]);
+        assert_eq!(chunks.next(), Some(&[0, 1, 2]));
+        assert_eq!(chunks.collect::<Vec<_>>(), [&[3, 4, 5], &[6, 7, 8]]);
+    }
+    #[test]
+    fn sort_by_key_orders_by_field() {
+        let mut array = HeapArray::from_slice(&[(3, "c"), (1, "a"), (2, "b"), (1, "z")]);
+        array.sort_by_key(|pair| pair.0);
+        assert_eq!(array.as_slice(), &[(1, "a"), (1, "z"), (2, "b"), (3, "c")]);
+        array.sort_by(|a, b| b.1.cmp(a.1));
+        assert_eq!(array.as_slice(), &[(1, "z"), (3, "c"), (2, "b"), (1, "a")]);
+        array.sort_unstable_by_key(|pair| pair.1);
+        assert_eq!(array.as_slice(), &[(1, "a"), (2, "b"), (3, "c"), (1, "z")]);
+    }
+    #[test]
+    fn scan_computes_prefix_sums() {
+        let array = HeapArray::from_slice(&[1, 2, 3, 4]);
+        let sums = array.scan(0, |acc, item| {
+            *acc += item;
+            *acc
+        });
+        assert_eq!(sums.as_slice(), &[1, 3, 6, 10]);
+        let units = HeapArray::<()>::new_default(3);
+        assert_eq!(units.scan(0, |count, _| {
+            *count += 1;
+            *count
+        }).as_slice(), &[1, 2, 3]);
+    }
+    #[test]
+    fn zip_with_combines_elementwise() {
+        let a = HeapArray::from_slice(&[1, 2, 3]);
+        let b = HeapArray::from_slice(&[10, 20, 30]);
+        assert_eq!(a.zip_with(&b, |x, y| x + y).as_slice(), &[11, 22, 33]);
+        let units = HeapArray::<()>::new_default(2);
+        assert_eq!(units.zip_with(&units, |_, _| 1).as_slice(), &[1, 1]);
+    }
+    #[test]
+    #[should_panic(expected = "cannot zip HeapArrays of different lengths: 1 and 2")]
+    fn zip_with_panics_on_length_mismatch() {
+        let _ = HeapArray::from_slice(&[1]).zip_with(&HeapArray::from_slice(&[1, 2]), |x, y| x + y);
+    }
+    #[test]
+    fn for_each_pair_mut_skips_trailing_element() {
+        let mut even = HeapArray::from_slice(&[1, 2, 3, 4]);
+        even.for_each_pair_mut(swap);
+        assert_eq!(even.as_slice(), &[2, 1, 4, 3]);
+        let mut odd = HeapArray::from_slice(&[1, 2, 3]);
+        odd.for_each_pair_mut(swap);
+        assert_eq!(odd.as_slice(), &[2, 1, 3]);
+        let mut single = HeapArray::from_slice(&[1]);
+        single.for_each_pair_mut(|_, _| panic!("no pairs expected"));
+    }
+    #[test]
+    fn compact_mutates_kept_and_drops_rejected() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((1..6).map(|i| DropCounter::new(i, &drops)));
+        let kept = array.compact(|item| {
+            item.value *= 2;
+            item.value % 4 == 0
+        });
+        assert_eq!(drops.get(), 3);
+        assert_eq!(kept.iter().map(|item| item.value).collect::<Vec<_>>(), [4, 8]);
+        drop(kept);
+        assert_eq!(drops.get(), 5);
+    }
+    #[test]
+    fn compact_is_panic_safe() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..4).map(|i| DropCounter::new(i, &drops)));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            array.compact(|item| {
+                if item.value == 2 {panic!("predicate failed");}
+                true
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    fn partition_point_finds_threshold() {
+        let array = HeapArray::from_slice(&[1, 3, 5, 7, 9]);
+        assert_eq!(array.partition_point(|&x| x < 6), 3);
+        assert_eq!(array.partition_point(|&x| x < 0), 0);
+        assert_eq!(array.partition_point(|&x| x < 100), 5);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).partition_point(|_| true), 0);
+    }
+    #[test]
+    fn argsort_returns_sorting_permutation() {
+        let array = HeapArray::from_slice(&[30, 10, 20]);
+        let order = array.argsort();
+        assert_eq!(order.as_slice(), &[1, 2, 0]);
+        assert_eq!(order.iter().map(|&i| array[i]).collect::<Vec<_>>(), [10, 20, 30]);
+        assert!(HeapArray::<i32>::from_slice(&[]).argsort().is_empty());
+    }
+    #[test]
+    fn try_map_converts_every_element() {
+        let array = HeapArray::from_slice(&["1", "2", "3"]);
+        let parsed = array.try_map(str::parse::<i32>).unwrap();
+        assert_eq!(parsed.as_slice(), &[1, 2, 3]);
+    }
+    #[test]
+    fn try_map_drops_everything_once_on_error() {
+        let inputs = Cell::new(0);
+        let outputs = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..5).map(|i| DropCounter::new(i, &inputs)));
+        let result = array.try_map(|item| {
+            if item.value == 2 {Err(item.value)} else {Ok(DropCounter::new(item.value * 10, &outputs))}
+        });
+        assert_eq!(result.err(), Some(2));
+        assert_eq!(inputs.get(), 5);
+        assert_eq!(outputs.get(), 2);
+    }
+    #[test]
+    fn peek_iter_looks_ahead_without_consuming() {
+        let array = HeapArray::from_slice(&[1, 2, 3]);
+        let mut iter = array.peek_iter();
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.peek_nth(2), Some(&3));
+        assert_eq!(iter.peek_nth(3), None);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.peek_nth(1), Some(&3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), [&2, &3]);
+        let units = HeapArray::<()>::new_default(2);
+        let mut iter = units.peek_iter();
+        assert_eq!(iter.peek_nth(1), Some(&()));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.peek(), None);
+    }
+    #[test]
+    fn into_vec_reuses_buffer() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let ptr = array.as_ptr();
+        let vec = array.into_vec();
+        assert_eq!(vec, ["a", "b"]);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(HeapArray::<()>::new_default(3).into_vec(), [(); 3]);
+        assert!(HeapArray::<String>::from_slice(&[]).into_vec().is_empty());
+    }
+    #[test]
+    fn into_vec_copies_over_aligned_buffers() {
+        let array = HeapArray::from_slice_aligned(&[1u8, 2, 3], 64);
+        assert_eq!(array.into_vec(), [1, 2, 3]);
+    }
+    #[test]
+    fn rchunks_put_short_chunk_last_from_the_front() {
+        let mut array = HeapArray::from_exact_iter(0..10);
+        let chunks: Vec<&[i32]> = array.rchunks(3).collect();
+        assert_eq!(chunks, [&[7, 8, 9][..], &[4, 5, 6], &[1, 2, 3], &[0]]);
+        for chunk in array.rchunks_mut(3) {
+            chunk[0] = -1;
+        }
+        assert_eq!(array.as_slice(), &[-1, -1, 2, 3, -1, 5, 6, -1, 8, 9]);
+    }
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn rchunks_panics_on_zero() {
+        let _ = HeapArray::from_slice(&[1]).rchunks(0);
+    }
+    #[test]
+    fn split_off_at_every_boundary() {
+        let strings = || HeapArray::from_slice(&["a".to_string(), "b".to_string(), "c".to_string()]);
+        let (head, tail) = strings().split_off(0);
+        assert!(head.is_empty());
+        assert_eq!(tail.as_slice(), &["a", "b", "c"]);
+        let (head, tail) = strings().split_off(3);
+        assert_eq!(head.as_slice(), &["a", "b", "c"]);
+        assert!(tail.is_empty());
+        let (head, tail) = strings().split_off(1);
+        assert_eq!(head.as_slice(), &["a"]);
+        assert_eq!(tail.as_slice(), &["b", "c"]);
+    }
+    #[test]
+    fn split_off_moves_each_element_once() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..4).map(|i| DropCounter::new(i, &drops)));
+        let (head, tail) = array.split_off(2);
+        assert_eq!(drops.get(), 0);
+        drop(head);
+        assert_eq!(drops.get(), 2);
+        drop(tail);
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    #[should_panic(expected = "split index out of bounds")]
+    fn split_off_panics_past_len() {
+        let _ = HeapArray::from_slice(&[1]).split_off(2);
+    }
+    #[test]
+    fn builder_collects_dynamic_count() {
+        let mut builder = HeapArrayBuilder::new();
+        assert!(builder.is_empty());
+        for i in (0..20).filter(|i| i % 3 == 0) {
+            builder.push(i.to_string());
+        }
+        assert_eq!(builder.len(), 7);
+        let array = builder.build();
+        assert_eq!(array.as_slice(), &["0", "3", "6", "9", "12", "15", "18"]);
+        assert!(HeapArrayBuilder::<String>::with_capacity(8).build().is_empty());
+        assert!(HeapArrayBuilder::<()>::default().build().is_empty());
+    }
+    #[test]
+    fn get_unchecked_reads_in_bounds() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        unsafe {
+            assert_eq!(*array.get_unchecked(2), 3);
+            *array.get_unchecked_mut(0) = 7;
+        }
+        assert_eq!(array.as_slice(), &[7, 2, 3]);
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn index_out_of_bounds_panics() {
+        let _ = HeapArray::from_slice(&[1, 2, 3])[3];
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 5")]
+    fn index_mut_out_of_bounds_panics() {
+        HeapArray::from_slice(&[1])[5] = 2;
+    }
+    #[test]
+    fn stride_iter_visits_every_step() {
+        let mut array = HeapArray::from_exact_iter(0..10);
+        assert_eq!(array.stride_iter(3).copied().collect::<Vec<_>>(), [0, 3, 6, 9]);
+        for item in array.stride_iter_mut(3) {
+            *item = -*item - 1;
+        }
+        assert_eq!(array.as_slice(), &[-1, 1, 2, -4, 4, 5, -7, 7, 8, -10]);
+    }
+    #[test]
+    #[should_panic(expected = "stride step must be non-zero")]
+    fn stride_iter_panics_on_zero() {
+        let _ = HeapArray::from_slice(&[1]).stride_iter(0);
+    }
+    #[test]
+    fn compares_with_fixed_size_arrays() {
+        let array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array, [1, 2, 3]);
+        assert_eq!([1, 2, 3], array);
+        assert_ne!(array, [1, 2]);
+        assert_ne!(array, [1, 2, 3, 4]);
+        assert_ne!([1, 2, 4], array);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]), []);
+    }
+    #[test]
+    fn new_uninit_then_assume_init() {
+        let mut array = HeapArray::<String>::new_uninit(3);
+        for (i, slot) in array.iter_mut().enumerate() {
+            slot.write(i.to_string());
+        }
+        let array = unsafe {array.assume_init()};
+        assert_eq!(array.as_slice(), &["0", "1", "2"]);
+        assert!(unsafe {HeapArray::<String>::new_uninit(0).assume_init()}.is_empty());
+        assert_eq!(unsafe {HeapArray::<()>::new_uninit(2).assume_init()}.len(), 2);
+    }
+    #[test]
+    fn fold_and_reduce_over_large_array() {
+        let array = HeapArray::from_exact_iter(0..100_000u32);
+        assert_eq!(array.fold(0u64, |acc, &x| acc + u64::from(x)), 4_999_950_000);
+        assert_eq!(array.reduce(|a, b| if b > a {b} else {a}), Some(&99_999));
+        let empty = HeapArray::<u32>::from_slice(&[]);
+        assert_eq!(empty.fold(7, |acc, _| acc + 1), 7);
+        assert_eq!(empty.reduce(|a, _| a), None);
+        assert_eq!(HeapArray::from_slice(&[5]).reduce(|_, _| unreachable!()), Some(&5));
+    }
+    #[test]
+    fn try_from_iter_collects_or_short_circuits() {
+        let ok: Result<HeapArray<i32>, String> = HeapArray::try_from_iter([Ok(1), Ok(2)]);
+        assert_eq!(ok.unwrap().as_slice(), &[1, 2]);
+        let drops = Cell::new(0);
+        let mut pulled = 0;
+        let items = (0..4).map(|i| {
+            pulled += 1;
+            if i == 1 {Err("bad")} else {Ok(DropCounter::new(i, &drops))}
+        });
+        assert_eq!(HeapArray::try_from_iter(items).err(), Some("bad"));
+        assert_eq!(pulled, 2);
+        assert_eq!(drops.get(), 1);
+    }
+    #[test]
+    fn enumerate_mut_pairs_indices_with_elements() {
+        let mut array = HeapArray::from_slice(&[10, 20, 30]);
+        let mut iter = array.enumerate_mut();
+        assert_eq!(iter.len(), 3);
+        let (i, first) = iter.next().unwrap();
+        assert_eq!((i, *first), (0, 10));
+        assert_eq!(iter.len(), 2);
+        for (i, item) in iter {
+            *item += i as i32;
+        }
+        assert_eq!(array.as_slice(), &[10, 21, 32]);
+        let mut units = HeapArray::<()>::new_default(2);
+        assert_eq!(units.enumerate_mut().map(|(i, _)| i).collect::<Vec<_>>(), [0, 1]);
+    }
+    #[test]
+    fn clone_into_vec_reuses_capacity() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let mut out = vec!["stale".to_string()];
+        array.clone_into_vec(&mut out);
+        assert_eq!(out, ["a", "b"]);
+        let (ptr, capacity) = (out.as_ptr(), out.capacity());
+        array.clone_into_vec(&mut out);
+        assert_eq!(out, ["a", "b"]);
+        assert_eq!((out.as_ptr(), out.capacity()), (ptr, capacity));
+    }
+    #[test]
+    fn reverse_handles_every_length_parity() {
+        let mut empty = HeapArray::<i32>::from_slice(&[]);
+        empty.reverse();
+        assert!(empty.is_empty());
+        let mut single = HeapArray::from_slice(&[1]);
+        single.reverse();
+        assert_eq!(single.as_slice(), &[1]);
+        let mut even = HeapArray::from_slice(&[1, 2, 3, 4]);
+        even.reverse();
+        assert_eq!(even.as_slice(), &[4, 3, 2, 1]);
+        let mut odd = HeapArray::from_slice(&["a".to_string(), "mid".to_string(), "c".to_string()]);
+        let middle = odd[1].as_ptr();
+        odd.reverse();
+        assert_eq!(odd.as_slice(), &["c", "mid", "a"]);
+        assert_eq!(odd[1].as_ptr(), middle);
+    }
+    #[test]
+    fn par_fill_small_array_fills_inline() {
+        let mut array = HeapArray::from_slice(&[0; 10]);
+        array.par_fill(7);
+        assert_eq!(array.as_slice(), &[7; 10]);
+        let mut empty = HeapArray::<i32>::from_slice(&[]);
+        empty.par_fill(1);
+        assert!(empty.is_empty());
+    }
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn par_fill_multi_million_elements() {
+        let mut array = HeapArray::new_default(4_000_000);
+        array.par_fill(0xA5u8);
+        assert!(array.iter().all(|&byte| byte == 0xA5));
+        let mut strings = HeapArray::<String>::new_default(1 << 16);
+        strings.par_fill("x".to_string());
+        assert!(strings.iter().all(|item| item == "x"));
+    }
+    #[test]
+    fn into_iter_is_double_ended() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()]);
+        let mut iter = array.into_iter();
+        assert_eq!(iter.next_back().as_deref(), Some("d"));
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), ["c", "b"]);
+    }
+    #[test]
+    fn into_iter_partial_consumption_drops_rest() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..5).map(|i| DropCounter::new(i, &drops)));
+        let mut iter = array.into_iter();
+        let first = iter.next().unwrap();
+        let last = iter.next_back().unwrap();
+        drop(iter);
+        assert_eq!(drops.get(), 3);
+        assert_eq!((first.value, last.value), (0, 4));
+        drop((first, last));
+        assert_eq!(drops.get(), 5);
+    }
+    struct PanicOnDrop<'a> {
+        panics: bool,
+        drops: &'a Cell<usize>,
+    }
+    impl Drop for PanicOnDrop<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panics {panic!("element drop panicked");}
+        }
+    }
+    #[test]
+    fn into_iter_frees_buffer_after_full_and_partial_consumption() {
+        let drops = Cell::new(0);
+        let make = || HeapArray::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops)));
+        assert_eq!(make().into_iter().count(), 3);
+        assert_eq!(drops.get(), 3);
+        let _ = make().into_iter().nth(1);
+        assert_eq!(drops.get(), 6);
+        drop(make().into_iter());
+        assert_eq!(drops.get(), 9);
+        assert_eq!(HeapArray::<()>::new_default(4).into_iter().count(), 4);
+        assert_eq!(HeapArray::<String>::from_slice(&[]).into_iter().count(), 0);
+    }
+    #[test]
+    fn into_iter_frees_buffer_when_element_drop_panics() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..4).map(|i| PanicOnDrop {panics: i == 1, drops: &drops}));
+        let mut iter = array.into_iter();
+        drop(iter.next());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || drop(iter)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    fn rotated_copies_leave_source_intact() {
+        let array = HeapArray::from_slice(&[1, 2, 3, 4]);
+        assert_eq!(array.rotated_left(1).as_slice(), &[2, 3, 4, 1]);
+        assert_eq!(array.rotated_right(1).as_slice(), &[4, 1, 2, 3]);
+        assert_eq!(array.rotated_left(6).as_slice(), &[3, 4, 1, 2]);
+        assert_eq!(array.rotated_right(9).as_slice(), &[4, 1, 2, 3]);
+        assert_eq!(array.rotated_left(4).as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(array.as_slice(), &[1, 2, 3, 4]);
+        assert!(HeapArray::<i32>::from_slice(&[]).rotated_left(3).is_empty());
+    }
+    #[test]
+    fn insert_sorted_keeps_order() {
+        let array = || HeapArray::from_slice(&[10, 20, 30]);
+        assert_eq!(array().insert_sorted(25).as_slice(), &[10, 20, 25, 30]);
+        assert_eq!(array().insert_sorted(5).as_slice(), &[5, 10, 20, 30]);
+        assert_eq!(array().insert_sorted(35).as_slice(), &[10, 20, 30, 35]);
+        assert_eq!(array().insert_sorted(20).as_slice(), &[10, 20, 20, 30]);
+        assert_eq!(HeapArray::from_slice(&[]).insert_sorted(1).as_slice(), &[1]);
+    }
+    #[test]
+    fn try_grow_appends_fill() {
+        let array = HeapArray::from_slice(&[1, 2]).try_grow(2, 0).unwrap();
+        assert_eq!(array.as_slice(), &[1, 2, 0, 0]);
+        assert_eq!(HeapArray::from_slice(&[1]).grow(1, 9).as_slice(), &[1, 9]);
+    }
+    #[test]
+    fn try_grow_returns_original_on_failure() {
+        let original = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let ptr = original.as_ptr();
+        let err = original.try_grow(usize::MAX, String::new()).unwrap_err();
+        assert_eq!(err.kind(), TryGrowErrorKind::CapacityOverflow);
+        let original = err.into_array();
+        assert_eq!(original.as_ptr(), ptr);
+        assert_eq!(original.as_slice(), &["a", "b"]);
+        let err = original.try_grow(isize::MAX as usize, String::new()).unwrap_err();
+        assert_eq!(err.into_array().as_slice(), &["a", "b"]);
+    }
+    #[test]
+    fn chunk_by_yields_runs() {
+        let array = HeapArray::from_slice(&[1, 1, 2, 2, 2, 3]);
+        let runs: Vec<&[i32]> = array.chunk_by(|a, b| a == b).collect();
+        assert_eq!(runs, [&[1, 1][..], &[2, 2, 2], &[3]]);
+        assert_eq!(array.chunk_by(|a, b| a <= b).count(), 1);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).chunk_by(|a, b| a == b).count(), 0);
+    }
+    #[test]
+    fn dedup_by_key_keeps_first_of_each_run() {
+        let words = ["a", "b", "cc", "dd", "e", "fff"].map(String::from);
+        let array = HeapArray::from_slice(&words);
+        assert_eq!(array.dedup_by_key(String::len).as_slice(), &["a", "cc", "e", "fff"]);
+    }
+    #[test]
+    fn dedup_by_key_drops_removed_once() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter([1, 1, 2, 1, 1].into_iter().map(|i| DropCounter::new(i, &drops)));
+        let kept = array.dedup_by_key(|item| item.value);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(kept.len(), 3);
+        drop(kept);
+        assert_eq!(drops.get(), 5);
+    }
+    #[test]
+    fn into_boxed_slice_round_trips() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let ptr = array.as_ptr();
+        let boxed = array.into_boxed_slice();
+        assert_eq!(boxed.as_ptr(), ptr);
+        let array = HeapArray::from_boxed_slice(boxed);
+        assert_eq!(array.as_ptr(), ptr);
+        assert_eq!(array.as_slice(), &["a", "b"]);
+        assert_eq!(HeapArray::<()>::new_default(3).into_boxed_slice().len(), 3);
+        assert!(HeapArray::<String>::from_slice(&[]).into_boxed_slice().is_empty());
+        assert_eq!(&*HeapArray::from_slice_aligned(&[1u16, 2], 32).into_boxed_slice(), &[1, 2]);
+    }
+    #[test]
+    fn get_clamped_and_get_wrapping_at_bounds() {
+        let array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.get_clamped(1), Some(&2));
+        assert_eq!(array.get_clamped(2), Some(&3));
+        assert_eq!(array.get_clamped(100), Some(&3));
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).get_clamped(0), None);
+        assert_eq!(*array.get_wrapping(2), 3);
+        assert_eq!(*array.get_wrapping(3), 1);
+        assert_eq!(*array.get_wrapping(usize::MAX), 1);
+    }
+    #[test]
+    #[should_panic(expected = "cannot index an empty HeapArray with wrapping")]
+    fn get_wrapping_panics_on_empty() {
+        let _ = HeapArray::<i32>::from_slice(&[]).get_wrapping(0);
+    }
+    #[test]
+    #[cfg(feature = "checksum")]
+    fn crc32_matches_known_value() {
+        assert_eq!(HeapArray::from_slice(b"123456789").crc32(), 0xCBF4_3926);
+        assert_eq!(HeapArray::<u8>::from_slice(&[]).crc32(), 0);
+        assert_eq!(HeapArray::from_slice(&[u32::from_ne_bytes(*b"1234")]).crc32(), HeapArray::from_slice(b"1234").crc32());
+    }
+    #[test]
+    fn as_chunks_splits_off_remainder() {
+        let mut array = HeapArray::from_exact_iter(0..7);
+        let (chunks, rem) = array.as_chunks::<2>();
+        assert_eq!(chunks, &[[0, 1], [2, 3], [4, 5]]);
+        assert_eq!(rem, &[6]);
+        let (chunks, rem) = array.as_chunks_mut::<2>();
+        for chunk in chunks {
+            chunk.swap(0, 1);
+        }
+        rem[0] = -1;
+        assert_eq!(array.as_slice(), &[1, 0, 3, 2, 5, 4, -1]);
+    }
+    #[test]
+    fn copied_and_cloned_yield_values() {
+        let mut numbers = HeapArray::from_slice(&[1u32, 2, 3]);
+        let total: u32 = numbers.copied().sum();
+        numbers[0] = total;
+        assert_eq!(numbers.as_slice(), &[6, 2, 3]);
+        let words = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        let owned: Vec<String> = words.cloned().collect();
+        drop(words);
+        assert_eq!(owned, ["a", "b"]);
+    }
+    #[test]
+    fn aligned_constructors_honor_alignment() {
+        let array = HeapArray::with_alignment(7u8, 100, 64);
+        assert_eq!(array.as_ptr().addr() % 64, 0);
+        assert_eq!(array.as_slice(), &[7; 100]);
+        let array = HeapArray::from_slice_aligned(&[1u32, 2, 3], 4096);
+        assert_eq!(array.as_ptr().addr() % 4096, 0);
+        assert_eq!(array.as_slice(), &[1, 2, 3]);
+        assert_eq!(HeapArray::<u8>::from_slice_aligned(&[], 128).as_ptr().addr() % 128, 0);
+    }
+    #[test]
+    #[should_panic(expected = "alignment must be a power of two, got 48")]
+    fn with_alignment_rejects_non_power_of_two() {
+        let _ = HeapArray::with_alignment(0u8, 1, 48);
+    }
+    #[test]
+    #[should_panic(expected = "alignment 2 is smaller than the alignment of the element type (8)")]
+    fn with_alignment_rejects_under_alignment() {
+        let _ = HeapArray::with_alignment(0u64, 1, 2);
+    }
+    #[test]
+    fn clone_preserves_custom_alignment() {
+        let array = HeapArray::from_slice_aligned(&["a".to_string(), "b".to_string()], 256);
+        let copy = array.clone();
+        assert_eq!(copy.as_ptr().addr() % 256, 0);
+        assert_eq!(copy.as_slice(), array.as_slice());
+        drop(array);
+        assert_eq!(copy.as_slice(), &["a", "b"]);
+    }
+    #[test]
+    fn clone_handles_zero_sized_and_empty_arrays() {
+        let units = HeapArray::<()>::new_default(5);
+        assert_eq!(units.clone().len(), 5);
+        assert!(HeapArray::<String>::from_slice(&[]).clone().is_empty());
+    }
+    #[test]
+    fn clone_is_panic_safe() {
+        struct Bomb(u32);
+        impl Clone for Bomb {
+            fn clone(&self) -> Self {
+                if self.0 == 2 {panic!("clone failed");}
+                Bomb(self.0)
+            }
+        }
+        let array = HeapArray::from_exact_iter((0..4).map(Bomb));
+        assert!(std::panic::catch_unwind(|| array.clone()).is_err());
+    }
+    #[test]
+    fn rev_enumerate_keeps_original_indices() {
+        let array = HeapArray::from_slice(&[10, 20, 30]);
+        assert_eq!(array.rev_enumerate().collect::<Vec<_>>(), [(2, &30), (1, &20), (0, &10)]);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).rev_enumerate().count(), 0);
+    }
+    #[test]
+    fn replace_returns_previous_value() {
+        let mut array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        assert_eq!(array.replace(1, "c".to_string()), "b");
+        assert_eq!(array.try_replace(0, "d".to_string()).as_deref(), Some("a"));
+        assert_eq!(array.try_replace(2, "e".to_string()), None);
+        assert_eq!(array.as_slice(), &["d", "c"]);
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn replace_panics_out_of_bounds() {
+        let _ = HeapArray::from_slice(&[1]).replace(1, 2);
+    }
+    #[test]
+    fn take_leaves_default_behind() {
+        let mut array = HeapArray::from_slice(&["a".to_string(), "b".to_string()]);
+        assert_eq!(array.take(1), "b");
+        assert!(array[1].is_empty());
+        assert_eq!(array.as_slice(), &["a", ""]);
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn take_panics_out_of_bounds() {
+        let _ = HeapArray::<String>::new_default(1).take(3);
+    }
+    #[test]
+    fn nth_jumps_on_every_iterator() {
+        let mut array = HeapArray::from_exact_iter(0..10);
+        let mut iter = array.iter();
+        assert_eq!(iter.nth(3), Some(&3));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.nth(5), Some(&9));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(array.iter().nth(10), None);
+        assert_eq!(array.iter().last(), Some(&9));
+        let mut iter = array.iter_mut();
+        *iter.nth(2).unwrap() = -1;
+        assert_eq!(iter.nth(100), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(array[2], -1);
+        let units = HeapArray::<()>::new_default(4);
+        let mut iter = units.iter();
+        assert_eq!(iter.nth(2), Some(&()));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth(1), None);
+    }
+    #[test]
+    fn into_iter_nth_drops_skipped_elements() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..6).map(|i| DropCounter::new(i, &drops)));
+        let mut iter = array.into_iter();
+        let third = iter.nth(2).unwrap();
+        assert_eq!((third.value, drops.get()), (2, 2));
+        assert!(iter.nth(10).is_none());
+        assert_eq!(drops.get(), 5);
+        drop(third);
+        drop(iter);
+        assert_eq!(drops.get(), 6);
+    }
+    #[test]
+    fn last_jumps_to_final_element() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.iter().last(), Some(&3));
+        *array.iter_mut().last().unwrap() = 9;
+        assert_eq!(array.as_slice(), &[1, 2, 9]);
+        let mut iter = array.iter();
+        iter.next();
+        assert_eq!(iter.last(), Some(&9));
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).iter().last(), None);
+        assert_eq!(HeapArray::<()>::new_default(3).iter().last(), Some(&()));
+        assert_eq!(array.into_iter().last(), Some(9));
+    }
+    #[test]
+    fn into_iter_last_drops_skipped_elements() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..4).map(|i| DropCounter::new(i, &drops)));
+        let last = array.into_iter().last().unwrap();
+        assert_eq!((last.value, drops.get()), (3, 3));
+        drop(last);
+        assert_eq!(drops.get(), 4);
+        assert!(HeapArray::<String>::from_slice(&[]).into_iter().last().is_none());
+    }
+    #[test]
+    fn split_at_checked_in_and_out_of_range() {
+        let mut array = HeapArray::from_slice(&[1, 2, 3]);
+        assert_eq!(array.split_at_checked(1), Some((&[1][..], &[2, 3][..])));
+        assert_eq!(array.split_at_checked(3), Some((&[1, 2, 3][..], &[][..])));
+        assert_eq!(array.split_at_checked(4), None);
+        let (head, tail) = array.split_at_mut_checked(2).unwrap();
+        head[0] = tail[0];
+        assert_eq!(array.as_slice(), &[3, 2, 3]);
+        assert!(array.split_at_mut_checked(4).is_none());
+    }
+    #[test]
+    fn try_from_slice_aligned_checks_the_pointer() {
+        let words = [1u32, 2, 3, 4];
+        let array = unsafe {HeapArray::try_from_slice_aligned(words.as_ptr(), 4)}.unwrap();
+        assert_eq!(array.as_slice(), &words);
+        let bytes = HeapArray::from_slice_aligned(&[0u8; 12], 4);
+        let misaligned = bytes.as_ptr().wrapping_add(1).cast::<u32>();
+        let err = unsafe {HeapArray::try_from_slice_aligned(misaligned, 2)}.unwrap_err();
+        assert_eq!(err, Misaligned {addr: misaligned.addr(), align: 4});
+        assert_eq!(err.to_string(), format!("address {:#x} is not aligned to 4 bytes", misaligned.addr()));
+    }
+    #[test]
+    fn clone_from_slice_refreshes_in_place() {
+        let mut array = HeapArray::from_slice(&["old".to_string(), "old".to_string()]);
+        let ptr = array.as_ptr();
+        array.clone_from_slice(&["a".to_string(), "b".to_string()]);
+        assert_eq!(array.as_slice(), &["a", "b"]);
+        assert_eq!(array.as_ptr(), ptr);
+    }
+    #[test]
+    #[should_panic(expected = "source slice length (1) does not match HeapArray length (2)")]
+    fn clone_from_slice_panics_on_mismatch() {
+        HeapArray::from_slice(&[1, 2]).clone_from_slice(&[1]);
+    }
+    #[test]
+    fn sort_dedup_returns_sorted_unique_values() {
+        assert_eq!(HeapArray::from_slice(&[3, 1, 2, 3, 1]).sort_dedup().as_slice(), &[1, 2, 3]);
+        assert!(HeapArray::<i32>::from_slice(&[]).sort_dedup().is_empty());
+        let words = HeapArray::from_slice(&["b".to_string(), "a".to_string(), "b".to_string()]);
+        assert_eq!(words.sort_dedup().as_slice(), &["a", "b"]);
+    }
+    #[test]
+    fn pairwise_yields_overlapping_pairs() {
+        let array = HeapArray::from_slice(&[1, 3, 6, 10]);
+        assert_eq!(array.pairwise().collect::<Vec<_>>(), [(&1, &3), (&3, &6), (&6, &10)]);
+        assert_eq!(array.pairwise().map(|(a, b)| b - a).collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(HeapArray::from_slice(&[1]).pairwise().count(), 0);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).pairwise().count(), 0);
+    }
+    #[test]
+    fn position_extrema_prefer_first_occurrence() {
+        let array = HeapArray::from_slice(&[2, 5, 1, 5, 1]);
+        assert_eq!(array.position_max(), Some(1));
+        assert_eq!(array.position_min(), Some(2));
+        let empty = HeapArray::<i32>::from_slice(&[]);
+        assert_eq!((empty.position_min(), empty.position_max()), (None, None));
+    }
+    #[test]
+    fn riter_mut_builds_suffix_max() {
+        let mut array = HeapArray::from_slice(&[3, 1, 4, 1, 5, 2]);
+        let mut best = i32::MIN;
+        for item in array.riter_mut() {
+            best = best.max(*item);
+            *item = best;
+        }
+        assert_eq!(array.as_slice(), &[5, 5, 5, 5, 5, 2]);
+    }
+    #[test]
+    fn is_sorted_variants() {
+        assert!(HeapArray::from_slice(&[1, 2, 2, 3]).is_sorted());
+        assert!(!HeapArray::from_slice(&[3, 2, 1]).is_sorted());
+        assert!(HeapArray::from_slice(&[7, 7, 7]).is_sorted());
+        assert!(HeapArray::<i32>::from_slice(&[]).is_sorted());
+        assert!(!HeapArray::from_slice(&[1.0, f64::NAN]).is_sorted());
+        assert!(HeapArray::from_slice(&[3, 2, 1]).is_sorted_by(|a, b| a >= b));
+        let mut calls = 0;
+        assert!(!HeapArray::from_slice(&[2, 1, 3, 4]).is_sorted_by(|a, b| {
+            calls += 1;
+            a <= b
+        }));
+        assert_eq!(calls, 1);
+    }
+    #[test]
+    fn chunks_exact_exposes_remainder() {
+        let mut array = HeapArray::from_exact_iter(0..7);
+        let chunks = array.chunks_exact(3);
+        assert_eq!(chunks.remainder(), &[6]);
+        assert_eq!(chunks.collect::<Vec<_>>(), [&[0, 1, 2][..], &[3, 4, 5]]);
+        let mut chunks = array.chunks_exact_mut(3);
+        for chunk in chunks.by_ref() {
+            chunk.reverse();
+        }
+        chunks.into_remainder()[0] = -1;
+        assert_eq!(array.as_slice(), &[2, 1, 0, 5, 4, 3, -1]);
+    }
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_exact_panics_on_zero() {
+        let _ = HeapArray::from_slice(&[1]).chunks_exact(0);
+    }
+    #[test]
+    fn concat_moves_owned_arrays() {
+        let drops = Cell::new(0);
+        let make = |range: Range<i32>| HeapArray::from_exact_iter(range.map(|i| DropCounter::new(i, &drops)));
+        let joined = HeapArray::concat(vec![make(0..2), make(2..2), make(2..5)]);
+        assert_eq!(drops.get(), 0);
+        assert_eq!(joined.iter().map(|item| item.value).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        drop(joined);
+        assert_eq!(drops.get(), 5);
+        let words = HeapArray::concat([
+            HeapArray::from_slice(&["a".to_string()]),
+            HeapArray::from_slice(&["b".to_string(), "c".to_string()])
+        ]);
+        assert_eq!(words.as_slice(), &["a", "b", "c"]);
+        assert!(HeapArray::<String>::concat([]).is_empty());
+    }
+    #[test]
+    #[cfg(feature = "cast")]
+    fn as_slice_of_reinterprets_bytes() {
+        let bytes = HeapArray::from_slice_aligned(&[1u8, 0, 0, 0, 2, 0, 0, 0], 4);
+        let words = bytes.as_slice_of::<u32>().unwrap();
+        assert_eq!(words, &[u32::from_ne_bytes([1, 0, 0, 0]), u32::from_ne_bytes([2, 0, 0, 0])]);
+        assert_eq!(bytes.as_slice_of::<u8>().unwrap(), bytes.as_slice());
+        let odd = HeapArray::from_slice_aligned(&[0u8; 6], 4);
+        assert_eq!(odd.as_slice_of::<u32>(), Err(CastError::SizeMismatch {len: 6, size: 4}));
+        assert!(HeapArray::<u8>::from_slice(&[]).as_slice_of::<u64>().unwrap().is_empty());
+        assert_eq!(CastError::SizeMismatch {len: 6, size: 4}.to_string(), "byte length 6 is not a multiple of the element size 4");
+        assert_eq!(CastError::Misaligned {addr: 0x11, align: 4}.to_string(), "address 0x11 is not aligned to 4 bytes");
+    }
+    #[test]
+    #[cfg(feature = "rand")]
+    fn shuffle_and_choose_are_deterministic_for_a_seed() {
+        use rand::{SeedableRng, rngs::StdRng};
+        let shuffled = |seed| {
+            let mut array = HeapArray::from_exact_iter(0..32);
+            array.shuffle(&mut StdRng::seed_from_u64(seed));
+            array
+        };
+        let first = shuffled(7);
+        assert_eq!(first.as_slice(), shuffled(7).as_slice());
+        assert_ne!(first.as_slice(), HeapArray::from_exact_iter(0..32).as_slice());
+        assert_eq!(first.clone().sort_dedup().as_slice(), HeapArray::from_exact_iter(0..32).as_slice());
+        let mut rng = StdRng::seed_from_u64(7);
+        let picked = *first.choose(&mut rng).unwrap();
+        assert_eq!(first.choose(&mut StdRng::seed_from_u64(7)), Some(&picked));
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).choose(&mut rng), None);
+    }
+    #[test]
+    fn into_indexed_iter_pairs_original_positions() {
+        let array = HeapArray::from_slice(&["a".to_string(), "b".to_string(), "c".to_string()]);
+        let pairs: Vec<(usize, String)> = array.into_indexed_iter().collect();
+        assert_eq!(pairs, [(0, "a".to_string()), (1, "b".to_string()), (2, "c".to_string())]);
+    }
+    #[test]
+    fn into_indexed_iter_partial_consumption_frees_everything() {
+        let drops = Cell::new(0);
+        let array = HeapArray::from_exact_iter((0..5).map(|i| DropCounter::new(i, &drops)));
+        let mut iter = array.into_indexed_iter();
+        let (index, item) = iter.nth(1).unwrap();
+        assert_eq!((index, item.value), (1, 1));
+        assert_eq!(drops.get(), 1);
+        drop(iter);
+        assert_eq!(drops.get(), 4);
+        drop(item);
+        assert_eq!(drops.get(), 5);
+    }
+    #[test]
+    fn max_capacity_scales_with_element_size() {
+        assert_eq!(HeapArray::<u8>::max_capacity(), isize::MAX as usize);
+        assert_eq!(HeapArray::<u64>::max_capacity(), isize::MAX as usize / 8);
+        assert_eq!(HeapArray::<[u8; 4096]>::max_capacity(), isize::MAX as usize / 4096);
+        assert_eq!(HeapArray::<()>::max_capacity(), usize::MAX);
+        let overflow = HeapArray::from_slice(&[0u64]).try_grow(HeapArray::<u64>::max_capacity(), 0);
+        assert!(overflow.is_err());
+    }
+    #[test]
+    fn iter_with_prev_carries_previous_element() {
+        let array = HeapArray::from_slice(&[1, 2, 2, 3]);
+        assert_eq!(array.iter_with_prev().collect::<Vec<_>>(), [(None, &1), (Some(&1), &2), (Some(&2), &2), (Some(&2), &3)]);
+        assert_eq!(HeapArray::<i32>::from_slice(&[]).iter_with_prev().count(), 0);
+    }
+}
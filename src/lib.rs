@@ -1,5 +1,10 @@
 pub mod prelude;
 pub mod heap_array;
 pub mod ring_buffer;
+pub mod shared_array;
+#[cfg(feature = "small-array")]
+pub mod small_array;
 pub mod fn_set;
-pub mod fn_map;
\ No newline at end of file
+pub mod fn_map;
+#[cfg(test)]
+mod test_util;
\ No newline at end of file
@@ -1,4 +1,7 @@
-pub use crate::heap_array::HeapArray;
+pub use crate::heap_array::{HeapArray, HeapArrayBuilder};
 pub use crate::ring_buffer::RingBuffer;
+pub use crate::shared_array::SharedArray;
+#[cfg(feature = "small-array")]
+pub use crate::small_array::SmallArray;
 pub use crate::fn_set::FnSet;
 pub use crate::fn_map::FnMap;
\ No newline at end of file
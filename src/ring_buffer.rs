@@ -1,4 +1,11 @@
-use crate::heap_array::HeapArray;
+use std::{
+    mem::MaybeUninit,
+    marker::PhantomData,
+    slice,
+    fmt::{Display, Formatter},
+    error::Error
+};
+use crate::heap_array::{HeapArray, IndexError};
 
 #[macro_export]
 macro_rules! ring {
@@ -10,14 +17,71 @@ macro_rules! ring {
     }}
 }
 
-pub struct RingBuffer<T> {
+/// Backing storage for a [`RingBuffer`].
+///
+/// # Safety
+///
+/// `RingBuffer` reads and drops the slots it has initialized through this trait, so an
+/// implementation must behave like a plain fixed-size slice:
+///
+/// * `as_slice` and `as_mut_slice` must always view the same slots, in the same order.
+/// * The number of slots must never change, and `len` must return it.
+/// * `get` and `get_mut` must return the slot at `index` of that slice.
+pub unsafe trait RingStorage<T> {
+    fn as_slice(&self) -> &[MaybeUninit<T>];
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>];
+    #[inline]
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    #[inline]
+    fn get(&self, index: usize) -> &MaybeUninit<T> {
+        &self.as_slice()[index]
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> &mut MaybeUninit<T> {
+        &mut self.as_mut_slice()[index]
+    }
+}
+unsafe impl<T> RingStorage<T> for HeapArray<MaybeUninit<T>> {
+    #[inline]
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        HeapArray::as_slice(self)
+    }
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        HeapArray::as_mut_slice(self)
+    }
+}
+unsafe impl<T, const N: usize> RingStorage<T> for [MaybeUninit<T>; N] {
+    #[inline]
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+pub struct RingBuffer<T, S: RingStorage<T> = HeapArray<MaybeUninit<T>>> {
     next_read: usize,
-    last_write: usize,
-    data: HeapArray<T>
+    len: usize,
+    consumed: usize,
+    overwrites: u64,
+    data: S,
+    _marker: PhantomData<T>
 }
-impl<T: Clone> RingBuffer<T> {
+impl<T: Clone, S: RingStorage<T>> RingBuffer<T, S> {
     pub fn to_vec(&mut self) -> Vec<T> {
-        self.read_all().into_iter().map(|x| x.clone()).collect()
+        self.read_all().into_iter().cloned().collect()
+    }
+    pub fn extend_from_slice(&mut self, src: &[T]) {
+        for value in src {self.write(value.clone());}
     }
 }
 impl<T: Clone> RingBuffer<T> {
@@ -25,40 +89,792 @@ impl<T: Clone> RingBuffer<T> {
     pub fn from_slice(slice: &[T]) -> RingBuffer<T> {
         RingBuffer {
             next_read: 0,
-            last_write: slice.len() - 1,
-            data: HeapArray::from_slice(slice)
+            len: slice.len(),
+            consumed: 0,
+            overwrites: 0,
+            data: HeapArray::from_exact_iter(slice.iter().map(|value| MaybeUninit::new(value.clone()))),
+            _marker: PhantomData
+        }
+    }
+}
+impl<T: PartialEq, S: RingStorage<T>> RingBuffer<T, S> {
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, x: &T) -> bool {
+        self.position(x).is_some()
+    }
+    #[must_use]
+    pub fn position(&self, x: &T) -> Option<usize> {
+        self.iter().position(|item| item == x)
+    }
+}
+impl<T, S: RingStorage<T>> RingBuffer<T, S> {
+    #[must_use]
+    pub fn with_storage(data: S) -> Self {
+        RingBuffer {
+            next_read: 0,
+            len: 0,
+            consumed: 0,
+            overwrites: 0,
+            data,
+            _marker: PhantomData
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == self.data.len()
+    }
+    #[inline]
+    #[must_use]
+    pub fn overwrite_count(&self) -> u64 {
+        self.overwrites
+    }
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.overwrites = 0;
+    }
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {return None;}
+        Some(unsafe {self.data.get(self.physical(index)).assume_init_ref()})
+    }
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {return None;}
+        let index = self.physical(index);
+        Some(unsafe {self.data.get_mut(index).assume_init_mut()})
+    }
+    #[inline]
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        let len = self.len;
+        self.get(index).ok_or(IndexError {index, len})
+    }
+    #[inline]
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        let len = self.len;
+        self.get_mut(index).ok_or(IndexError {index, len})
+    }
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter {
+            ring: self,
+            pos: 0,
+        }
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = if self.len == 0 {(Default::default(), Default::default())}
+        else {
+            let capacity = self.data.len();
+            let end = self.next_read + self.len;
+            let (front, back) = self.data.as_mut_slice().split_at_mut(self.next_read);
+            let head = &mut back[..end.min(capacity) - self.next_read];
+            let tail = &mut front[..end.saturating_sub(capacity)];
+            unsafe {(assume_init_slice_mut(head), assume_init_slice_mut(tail))}
+        };
+        IterMut {
+            head: head.iter_mut(),
+            tail: tail.iter_mut(),
         }
     }
     #[must_use]
+    pub fn as_contiguous_slice(&self) -> Option<&[T]> {
+        let (head, tail) = self.peek_slices();
+        if tail.is_empty() {Some(head)}
+        else {None}
+    }
+    #[must_use]
+    pub fn peek_slices(&self) -> (&[T], &[T]) {
+        if self.len == 0 {return (&[], &[]);}
+        let capacity = self.data.len();
+        let end = self.next_read + self.len;
+        let head = &self.data.as_slice()[self.next_read..end.min(capacity)];
+        let tail = &self.data.as_slice()[..end.saturating_sub(capacity)];
+        unsafe {(assume_init_slice(head), assume_init_slice(tail))}
+    }
+    #[must_use]
     pub fn read(&mut self) -> Option<&T> {
-        if self.next_read == (self.last_write + 1) % self.data.len() {
+        self.release();
+        if self.len == 0 {
             None
         }
         else {
-            let value = &self.data[self.next_read];
-            self.next_read = (self.next_read + 1) % self.data.len();
-            Some(value)
+            let index = self.next_read;
+            self.next_read = self.wrap(self.next_read + 1);
+            self.len -= 1;
+            self.consumed = 1;
+            Some(unsafe {self.data.get(index).assume_init_ref()})
         }
     }
-    pub fn write(&mut self, value: T) -> () {
-        let next_last_write = (self.last_write + 1) % self.data.len();
-        self.data[next_last_write] = value;
-        if self.next_read == next_last_write {
-            self.next_read = (self.next_read + 1) % self.data.len();
+    pub fn write(&mut self, value: T) {
+        drop(self.push(value));
+    }
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.release();
+        if self.data.is_empty() {return Some(value);}
+        if self.is_full() {
+            let evicted = unsafe {self.data.get(self.next_read).assume_init_read()};
+            *self.data.get_mut(self.next_read) = MaybeUninit::new(value);
+            self.next_read = self.wrap(self.next_read + 1);
+            self.overwrites += 1;
+            Some(evicted)
+        }
+        else {
+            let index = self.physical(self.len);
+            *self.data.get_mut(index) = MaybeUninit::new(value);
+            self.len += 1;
+            None
         }
     }
     #[must_use]
     pub fn read_all(&mut self) -> Vec<&T> {
-        let mut result = Vec::<&T>::new();
-        while self.next_read != (self.last_write + 1) % self.data.len() {
-            result.push(&self.data[self.next_read]);
-            self.next_read = (self.next_read + 1) % self.data.len();
+        self.release();
+        let start = self.next_read;
+        let len = self.len;
+        if len != 0 {
+            self.next_read = self.physical(len);
+            self.consumed = len;
+            self.len = 0;
+        }
+        let mut result = Vec::<&T>::with_capacity(len);
+        for i in 0..len {
+            let index = self.wrap(start + i);
+            result.push(unsafe {self.data.get(index).assume_init_ref()});
         }
         result
     }
-    pub fn write_all(&mut self, values: Vec<T>) -> () {
+    pub fn write_all(&mut self, values: Vec<T>) {
+        for value in values {self.write(value);}
+    }
+    pub fn push_all_returning_evicted(&mut self, values: Vec<T>) -> Vec<T> {
+        values.into_iter().filter_map(|value| self.push(value)).collect()
+    }
+    pub fn try_write(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            Err(value)
+        }
+        else {
+            self.write(value);
+            Ok(())
+        }
+    }
+    pub fn try_write_all(&mut self, mut values: Vec<T>) -> Result<(), Vec<T>> {
+        let free = self.data.len() - self.len;
+        let rest = if values.len() > free {values.split_off(free)} else {Vec::new()};
         for value in values {self.write(value);}
+        if rest.is_empty() {Ok(())}
+        else {Err(rest)}
+    }
+    pub fn drain(&mut self) -> Vec<T> {
+        self.release();
+        let mut result = Vec::with_capacity(self.len);
+        for i in 0..self.len {
+            let index = self.physical(i);
+            result.push(unsafe {self.data.get(index).assume_init_read()});
+        }
+        self.len = 0;
+        result
+    }
+    pub fn reset(&mut self) {
+        self.release();
+        let (start, len) = (self.next_read, self.len);
+        self.next_read = 0;
+        self.len = 0;
+        for i in 0..len {
+            let index = self.wrap(start + i);
+            unsafe {self.data.get_mut(index).assume_init_drop();}
+        }
+    }
+    #[inline]
+    fn physical(&self, index: usize) -> usize {
+        self.wrap(self.next_read + index)
+    }
+    #[inline]
+    fn wrap(&self, index: usize) -> usize {
+        index.checked_rem(self.data.len()).unwrap_or(0)
+    }
+    fn release(&mut self) {
+        let capacity = self.data.len();
+        for i in 0..self.consumed {
+            let index = self.wrap(self.next_read + capacity - self.consumed + i);
+            unsafe {self.data.get_mut(index).assume_init_drop();}
+        }
+        self.consumed = 0;
+    }
+}
+impl<T> RingBuffer<T> {
+    pub fn from_heap_array(data: HeapArray<T>, treat_as: TreatAs) -> Self {
+        let mut data = data.into_uninit();
+        let len = match treat_as {
+            TreatAs::Fill => data.len(),
+            TreatAs::Empty => {
+                for slot in data.iter_mut() {
+                    unsafe {slot.assume_init_drop();}
+                }
+                0
+            }
+        };
+        RingBuffer {
+            next_read: 0,
+            len,
+            consumed: 0,
+            overwrites: 0,
+            data,
+            _marker: PhantomData
+        }
+    }
+    pub fn resize(&mut self, new_cap: usize) {
+        if new_cap == self.data.len() {return;}
+        self.release();
+        let skip = self.len.saturating_sub(new_cap);
+        for i in 0..skip {
+            let index = self.physical(i);
+            unsafe {self.data[index].assume_init_drop();}
+        }
+        let mut data = HeapArray::new_uninit(new_cap);
+        for i in skip..self.len {
+            let index = self.physical(i);
+            data[i - skip] = MaybeUninit::new(unsafe {self.data[index].assume_init_read()});
+        }
+        self.data = data;
+        self.next_read = 0;
+        self.len -= skip;
+    }
+    pub fn shrink_to(&mut self, new_cap: usize) {
+        if new_cap < self.data.len() {
+            self.resize(new_cap);
+        }
+    }
+    pub fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> RingBuffer<U> {
+        let mut data = HeapArray::new_uninit(self.data.len());
+        for i in 0..self.len {
+            let index = self.physical(i);
+            data[index] = MaybeUninit::new(f(unsafe {self.data[index].assume_init_ref()}));
+        }
+        RingBuffer {
+            next_read: self.next_read,
+            len: self.len,
+            consumed: 0,
+            overwrites: 0,
+            data,
+            _marker: PhantomData
+        }
+    }
+    pub fn into_parts(mut self) -> (usize, usize, HeapArray<Option<T>>) {
+        self.release();
+        let mut slots = HeapArray::new_default(self.data.len());
+        for i in 0..self.len {
+            let index = self.physical(i);
+            slots[index] = Some(unsafe {self.data[index].assume_init_read()});
+        }
+        let parts = (self.next_read, self.len, slots);
+        self.len = 0;
+        parts
+    }
+    pub fn from_parts(next_read: usize, len: usize, slots: HeapArray<Option<T>>) -> Result<Self, PartsError> {
+        let capacity = slots.len();
+        if len > capacity {
+            return Err(PartsError::LenOutOfBounds {len, capacity});
+        }
+        if next_read >= capacity && !(capacity == 0 && next_read == 0) {
+            return Err(PartsError::ReadOutOfBounds {next_read, capacity});
+        }
+        for i in 0..len {
+            let index = (next_read + i) % capacity;
+            if slots[index].is_none() {
+                return Err(PartsError::MissingElement {index});
+            }
+        }
+        let data = HeapArray::from_exact_iter(slots.into_iter().enumerate().map(|(index, slot)| {
+            match slot {
+                Some(value) if (index + capacity - next_read) % capacity < len => MaybeUninit::new(value),
+                _ => MaybeUninit::uninit()
+            }
+        }));
+        Ok(RingBuffer {
+            next_read,
+            len,
+            consumed: 0,
+            overwrites: 0,
+            data,
+            _marker: PhantomData
+        })
+    }
+}
+impl<T, S: RingStorage<T>> Drop for RingBuffer<T, S> {
+    fn drop(&mut self) {
+        self.release();
+        for i in 0..self.len {
+            let index = self.physical(i);
+            unsafe {self.data.get_mut(index).assume_init_drop();}
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, S: RingStorage<T>> serde::Serialize for RingBuffer<T, S> {
+    fn serialize<Se: serde::Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for RingBuffer<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        Ok(Self::from_heap_array(HeapArray::from_boxed_slice(values.into_boxed_slice()), TreatAs::Fill))
+    }
+}
+unsafe fn assume_init_slice<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    unsafe {&*(slice as *const [MaybeUninit<T>] as *const [T])}
+}
+unsafe fn assume_init_slice_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    unsafe {&mut *(slice as *mut [MaybeUninit<T>] as *mut [T])}
+}
+unsafe impl<T: Send, S: RingStorage<T> + Send> Send for RingBuffer<T, S> {}
+unsafe impl<T: Sync, S: RingStorage<T> + Sync> Sync for RingBuffer<T, S> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreatAs {
+    Fill,
+    Empty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartsError {
+    LenOutOfBounds {len: usize, capacity: usize},
+    ReadOutOfBounds {next_read: usize, capacity: usize},
+    MissingElement {index: usize},
+}
+impl Display for PartsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenOutOfBounds {len, capacity} => write!(f, "length {} exceeds the capacity {}", len, capacity),
+            Self::ReadOutOfBounds {next_read, capacity} => write!(f, "read position {} is out of bounds for the capacity {}", next_read, capacity),
+            Self::MissingElement {index} => write!(f, "readable slot {} holds no element", index),
+        }
+    }
+}
+impl Error for PartsError {}
+
+pub struct Iter<'a, T, S: RingStorage<T> = HeapArray<MaybeUninit<T>>> {
+    ring: &'a RingBuffer<T, S>,
+    pos: usize,
+}
+impl<'a, T, S: RingStorage<T>> Iterator for Iter<'a, T, S> {
+    type Item = &'a T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.ring.len {
+            None
+        } else {
+            let item = unsafe {self.ring.data.get(self.ring.physical(self.pos)).assume_init_ref()};
+            self.pos += 1;
+            Some(item)
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring.len - self.pos;
+        (len, Some(len))
+    }
+}
+impl<'a, T, S: RingStorage<T>> ExactSizeIterator for Iter<'a, T, S> {}
+
+pub struct IterMut<'a, T> {
+    head: slice::IterMut<'a, T>,
+    tail: slice::IterMut<'a, T>,
+}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head.next().or_else(|| self.tail.next())
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::DropCounter;
+    use std::cell::Cell;
+
+    fn wrapped() -> RingBuffer<i32> {
+        let mut ring = RingBuffer::from_slice(&[1, 2, 3, 4]);
+        let _ = ring.read();
+        let _ = ring.read();
+        ring.write(5);
+        ring
+    }
+    #[test]
+    fn resize_grows_wrapped_buffer() {
+        let mut ring = wrapped();
+        assert!(ring.as_contiguous_slice().is_none());
+        ring.resize(6);
+        assert_eq!(ring.capacity(), 6);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+        ring.write_all(vec![6, 7, 8]);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [3, 4, 5, 6, 7, 8]);
+    }
+    #[test]
+    fn resize_shrinks_by_dropping_oldest() {
+        let mut ring = wrapped();
+        ring.resize(2);
+        assert_eq!(ring.capacity(), 2);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [4, 5]);
+        ring.resize(0);
+        assert!(ring.is_empty());
+    }
+    #[test]
+    fn resize_drops_each_evicted_element_once() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::from_slice(&[]);
+        ring.resize(4);
+        for value in 0..6 {
+            ring.write(DropCounter::new(value, &drops));
+        }
+        assert_eq!(drops.get(), 2);
+        ring.resize(1);
+        assert_eq!(drops.get(), 5);
+        assert_eq!(ring.iter().map(|item| item.value).collect::<Vec<_>>(), [5]);
+        drop(ring);
+        assert_eq!(drops.get(), 6);
+    }
+    #[test]
+    fn try_write_all_returns_remainder_in_order() {
+        let mut ring = wrapped();
+        ring.resize(5);
+        assert_eq!(ring.try_write_all(vec![6, 7, 8, 9]), Err(vec![8, 9]));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [3, 4, 5, 6, 7]);
+        assert_eq!(ring.overwrite_count(), 0);
+        assert_eq!(ring.try_write_all(vec![]), Ok(()));
+    }
+    #[test]
+    fn try_write_all_fits_entirely() {
+        let mut ring = RingBuffer::from_slice(&[0; 3]);
+        let _ = ring.drain();
+        assert_eq!(ring.try_write_all(vec![1, 2, 3]), Ok(()));
+        assert!(ring.is_full());
+    }
+    #[test]
+    fn try_get_uses_logical_indices() {
+        let mut ring = wrapped();
+        assert_eq!(ring.try_get(0), Ok(&3));
+        assert_eq!(ring.try_get(2), Ok(&5));
+        *ring.try_get_mut(1).unwrap() = 9;
+        assert_eq!(ring.get(1), Some(&9));
+        assert_eq!(ring.try_get(3), Err(IndexError {index: 3, len: 3}));
+        assert_eq!(ring.try_get_mut(4).unwrap_err().len, 3);
+    }
+    #[test]
+    fn iter_does_not_consume() {
+        let mut ring = wrapped();
+        let first: Vec<i32> = ring.iter().copied().collect();
+        let second: Vec<i32> = ring.iter().copied().collect();
+        assert_eq!(first, [3, 4, 5]);
+        assert_eq!(first, second);
+        assert_eq!(ring.read(), Some(&3));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [4, 5]);
+    }
+    #[test]
+    fn drain_moves_wrapped_contents() {
+        let mut ring = RingBuffer::from_slice(&["a".to_string(), "b".to_string(), "c".to_string()]);
+        let _ = ring.read();
+        ring.write("d".to_string());
+        assert_eq!(ring.drain(), ["b", "c", "d"]);
+        assert!(ring.is_empty());
+        assert_eq!(ring.read(), None);
+        assert!(ring.drain().is_empty());
+    }
+    #[test]
+    fn drain_drops_each_element_once() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 3]);
+        for i in 0..4 {
+            ring.write(DropCounter::new(i, &drops));
+        }
+        assert_eq!(drops.get(), 1);
+        let drained = ring.drain();
+        assert_eq!(drained.iter().map(|item| item.value).collect::<Vec<_>>(), [1, 2, 3]);
+        drop(ring);
+        assert_eq!(drops.get(), 1);
+        drop(drained);
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    fn push_returns_evicted_once_full() {
+        let mut ring = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 2]);
+        assert_eq!(ring.push(1), None);
+        assert_eq!(ring.push(2), None);
+        assert_eq!(ring.push(3), Some(1));
+        assert_eq!(ring.push(4), Some(2));
+        assert_eq!(ring.overwrite_count(), 2);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [3, 4]);
+        let mut empty = RingBuffer::<i32, [MaybeUninit<i32>; 0]>::with_storage([]);
+        assert_eq!(empty.push(5), Some(5));
+    }
+    #[test]
+    fn parts_round_trip_preserves_wrapped_state() {
+        let (next_read, len, slots) = wrapped().into_parts();
+        assert_eq!((next_read, len), (2, 3));
+        assert_eq!(slots.as_slice(), &[Some(5), None, Some(3), Some(4)]);
+        let mut ring = RingBuffer::from_parts(next_read, len, slots).unwrap();
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+        ring.write(6);
+        assert_eq!(ring.drain(), [3, 4, 5, 6]);
+    }
+    #[test]
+    fn from_parts_rejects_inconsistent_state() {
+        let slots = || HeapArray::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(RingBuffer::from_parts(0, 4, slots()).err(), Some(PartsError::LenOutOfBounds {len: 4, capacity: 3}));
+        assert_eq!(RingBuffer::from_parts(3, 1, slots()).err(), Some(PartsError::ReadOutOfBounds {next_read: 3, capacity: 3}));
+        assert_eq!(RingBuffer::from_parts(0, 2, slots()).err(), Some(PartsError::MissingElement {index: 1}));
+        assert!(RingBuffer::from_parts(2, 2, slots()).is_ok());
+        assert!(RingBuffer::<i32>::from_parts(0, 0, HeapArray::from_slice(&[])).is_ok());
+    }
+    #[test]
+    fn parts_drop_every_element_once() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::from_heap_array(HeapArray::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops))), TreatAs::Fill);
+        let _ = ring.read();
+        let (next_read, len, slots) = ring.into_parts();
+        assert_eq!(drops.get(), 1);
+        let mut slots = slots;
+        slots[0] = Some(DropCounter::new(9, &drops));
+        let ring = RingBuffer::from_parts(next_read, len, slots).unwrap();
+        assert_eq!(drops.get(), 2);
+        drop(ring);
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    fn contains_ignores_stale_slots() {
+        let mut ring = RingBuffer::from_slice(&[1, 2, 3]);
+        let _ = ring.read();
+        assert!(!ring.contains(&1));
+        assert_eq!(ring.position(&1), None);
+        assert_eq!(ring.position(&3), Some(1));
+        ring.write(4);
+        assert!(ring.contains(&4));
+        assert_eq!(ring.position(&4), Some(2));
+    }
+    #[test]
+    fn overwrite_count_tracks_lossy_writes() {
+        let mut ring = RingBuffer::from_slice(&[1, 2]);
+        assert_eq!(ring.overwrite_count(), 0);
+        for value in 3..8 {
+            ring.write(value);
+        }
+        assert_eq!(ring.overwrite_count(), 5);
+        let _ = ring.read();
+        ring.write(8);
+        assert_eq!(ring.overwrite_count(), 5);
+        ring.reset_stats();
+        assert_eq!(ring.overwrite_count(), 0);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [7, 8]);
+    }
+    #[test]
+    fn extend_from_slice_keeps_newest_window() {
+        let mut ring = RingBuffer::from_slice(&[0; 4]);
+        let _ = ring.read();
+        let _ = ring.read();
+        ring.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+        assert_eq!(ring.overwrite_count(), 3);
+    }
+    #[test]
+    fn contiguous_and_wrapped_slices() {
+        let mut ring = RingBuffer::from_slice(&[1, 2, 3, 4]);
+        let _ = ring.read();
+        assert_eq!(ring.as_contiguous_slice(), Some(&[2, 3, 4][..]));
+        assert_eq!(ring.peek_slices(), (&[2, 3, 4][..], &[][..]));
+        let ring = wrapped();
+        assert_eq!(ring.as_contiguous_slice(), None);
+        assert_eq!(ring.peek_slices(), (&[3, 4][..], &[5][..]));
+        let empty = RingBuffer::<i32, [MaybeUninit<i32>; 2]>::with_storage([const {MaybeUninit::uninit()}; 2]);
+        assert_eq!(empty.as_contiguous_slice(), Some(&[][..]));
+        assert_eq!(empty.peek_slices(), (&[][..], &[][..]));
+    }
+    #[test]
+    fn zero_capacity_ring_never_panics() {
+        let mut ring = RingBuffer::<i32>::from_slice(&[]);
+        assert_eq!((ring.len(), ring.capacity()), (0, 0));
+        assert!(ring.is_empty());
+        assert!(ring.is_full());
+        assert_eq!(ring.read(), None);
+        ring.write(1);
+        assert_eq!(ring.push(2), Some(2));
+        assert_eq!(ring.try_write(3), Err(3));
+        assert_eq!(ring.try_write_all(vec![4, 5]), Err(vec![4, 5]));
+        ring.write_all(vec![6]);
+        ring.extend_from_slice(&[7]);
+        assert_eq!(ring.push_all_returning_evicted(vec![8, 9]), [8, 9]);
+        assert!(ring.read_all().is_empty());
+        assert!(ring.drain().is_empty());
+        assert!(ring.to_vec().is_empty());
+        assert_eq!(ring.get(0), None);
+        assert_eq!(ring.get_mut(0), None);
+        assert_eq!(ring.try_get(0), Err(IndexError {index: 0, len: 0}));
+        assert!(!ring.contains(&1));
+        assert_eq!(ring.position(&1), None);
+        assert_eq!(ring.iter().count(), 0);
+        assert_eq!(ring.iter_mut().count(), 0);
+        assert_eq!(ring.as_contiguous_slice(), Some(&[][..]));
+        assert_eq!(ring.peek_slices(), (&[][..], &[][..]));
+        assert_eq!(ring.map(|x| x * 2).capacity(), 0);
+        ring.reset();
+        ring.resize(2);
+        ring.write(10);
+        assert_eq!(ring.read(), Some(&10));
+        ring.shrink_to(0);
+        assert_eq!(ring.capacity(), 0);
+        assert_eq!(ring.into_parts().1, 0);
+    }
+    #[test]
+    fn map_preserves_logical_order() {
+        let ring = wrapped();
+        let doubled = ring.map(|x| x * 2);
+        assert_eq!(doubled.capacity(), 4);
+        assert_eq!(doubled.iter().copied().collect::<Vec<_>>(), [6, 8, 10]);
+        assert_eq!(doubled.peek_slices(), (&[6, 8][..], &[10][..]));
+        let strings = ring.map(i32::to_string);
+        assert_eq!(strings.iter().cloned().collect::<Vec<_>>(), ["3", "4", "5"]);
+    }
+    #[test]
+    fn reset_empties_and_keeps_capacity() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 3]);
+        for i in 0..4 {
+            ring.write(DropCounter::new(i, &drops));
+        }
+        assert!(ring.is_full());
+        ring.reset();
+        assert_eq!(drops.get(), 4);
+        assert!(ring.is_empty());
+        assert_eq!(ring.capacity(), 3);
+        for i in 10..13 {
+            ring.write(DropCounter::new(i, &drops));
+        }
+        assert_eq!(ring.iter().map(|item| item.value).collect::<Vec<_>>(), [10, 11, 12]);
+        assert_eq!(drops.get(), 4);
+    }
+    #[test]
+    fn push_all_returns_evictions_in_order() {
+        let mut ring = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 3]);
+        let evicted = ring.push_all_returning_evicted((0..6).map(|i| i.to_string()).collect());
+        assert_eq!(evicted, ["0", "1", "2"]);
+        assert_eq!(ring.iter().cloned().collect::<Vec<_>>(), ["3", "4", "5"]);
+        assert!(ring.push_all_returning_evicted(Vec::new()).is_empty());
+    }
+    #[test]
+    fn heap_and_array_storage_behave_alike() {
+        let mut heap = RingBuffer::from_heap_array(HeapArray::new_default(3), TreatAs::Empty);
+        let mut stack = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 3]);
+        for value in 1..=5 {
+            assert_eq!(heap.push(value), stack.push(value));
+        }
+        assert_eq!(heap.capacity(), stack.capacity());
+        assert_eq!(heap.iter().collect::<Vec<_>>(), stack.iter().collect::<Vec<_>>());
+        assert_eq!(heap.read(), Some(&3));
+        assert_eq!(stack.read(), Some(&3));
+        assert_eq!(heap.peek_slices(), stack.peek_slices());
+    }
+    #[test]
+    fn array_storage_drops_remaining_elements() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::with_storage([const {MaybeUninit::uninit()}; 2]);
+        for i in 0..3 {
+            ring.write(DropCounter::new(i, &drops));
+        }
+        let _ = ring.read();
+        assert_eq!(drops.get(), 1);
+        drop(ring);
+        assert_eq!(drops.get(), 3);
+    }
+    #[test]
+    fn get_skips_stale_slots_on_partially_full_ring() {
+        let mut ring = wrapped();
+        let _ = ring.read();
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.get(0), Some(&4));
+        assert_eq!(ring.get(1), Some(&5));
+        assert_eq!(ring.get(2), None);
+        assert_eq!(ring.get(usize::MAX), None);
+    }
+    #[test]
+    fn from_heap_array_fill_and_empty() {
+        let mut full = RingBuffer::from_heap_array(HeapArray::from_slice(&[1, 2, 3]), TreatAs::Fill);
+        assert!(full.is_full());
+        assert_eq!(full.read(), Some(&1));
+        let drops = Cell::new(0);
+        let data = HeapArray::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops)));
+        let mut empty = RingBuffer::from_heap_array(data, TreatAs::Empty);
+        assert_eq!(drops.get(), 3);
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 3);
+        empty.write(DropCounter::new(7, &drops));
+        assert_eq!(empty.read().map(|item| item.value), Some(7));
+    }
+    #[test]
+    fn shrink_to_keeps_newest_and_drops_oldest_once() {
+        let drops = Cell::new(0);
+        let mut ring = RingBuffer::from_heap_array(HeapArray::from_exact_iter((0..4).map(|i| DropCounter::new(i, &drops))), TreatAs::Fill);
+        let _ = ring.read();
+        ring.write(DropCounter::new(4, &drops));
+        assert_eq!(drops.get(), 1);
+        ring.shrink_to(2);
+        assert_eq!(drops.get(), 3);
+        assert_eq!(ring.capacity(), 2);
+        assert_eq!(ring.iter().map(|item| item.value).collect::<Vec<_>>(), [3, 4]);
+        ring.shrink_to(5);
+        assert_eq!(ring.capacity(), 2);
+        drop(ring);
+        assert_eq!(drops.get(), 5);
+    }
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip_uses_logical_order() {
+        let json = serde_json::to_string(&wrapped()).unwrap();
+        assert_eq!(json, "[3,4,5]");
+        let mut restored: RingBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_full());
+        assert_eq!(restored.capacity(), 3);
+        assert_eq!(restored.read(), Some(&3));
+        assert_eq!(restored.iter().copied().collect::<Vec<_>>(), [4, 5]);
+        let stack = RingBuffer::with_storage([const {MaybeUninit::new(1)}; 2]);
+        assert_eq!(serde_json::to_string(&stack).unwrap(), "[]");
+    }
+    #[test]
+    fn iter_mut_crosses_the_wrap() {
+        let mut ring = wrapped();
+        assert_eq!(ring.iter_mut().len(), 3);
+        for item in ring.iter_mut() {
+            *item *= 10;
+        }
+        assert_eq!(ring.read(), Some(&30));
+        assert_eq!(ring.read(), Some(&40));
+        assert_eq!(ring.read(), Some(&50));
+        assert_eq!(ring.read(), None);
     }
 }
-unsafe impl<T: Send> Send for RingBuffer<T> {}
-unsafe impl<T: Sync> Sync for RingBuffer<T> {}
\ No newline at end of file
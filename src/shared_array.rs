@@ -0,0 +1,93 @@
+use std::{
+    ops::Index,
+    sync::Arc,
+    fmt::Debug
+};
+use crate::heap_array::{HeapArray, Iter};
+
+pub struct SharedArray<T> {
+    data: Arc<HeapArray<T>>
+}
+impl<T> SharedArray<T> {
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.data.get(index)
+    }
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.data.iter()
+    }
+}
+impl<T> Clone for SharedArray<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data)
+        }
+    }
+}
+impl<T> From<HeapArray<T>> for SharedArray<T> {
+    fn from(array: HeapArray<T>) -> Self {
+        Self {
+            data: Arc::new(array)
+        }
+    }
+}
+impl<T> Index<usize> for SharedArray<T> {
+    type Output = T;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
+    }
+}
+impl<'a, T> IntoIterator for &'a SharedArray<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Debug> Debug for SharedArray<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::scope;
+
+    #[test]
+    fn clones_share_one_buffer_across_threads() {
+        let shared = HeapArray::from_exact_iter(0..1000u32).into_shared();
+        let addr = shared.as_slice().as_ptr().addr();
+        scope(|s| {
+            for _ in 0..4 {
+                let view = shared.clone();
+                s.spawn(move || {
+                    assert_eq!(view.as_slice().as_ptr().addr(), addr);
+                    assert_eq!(view.iter().sum::<u32>(), 499_500);
+                    assert_eq!(view[999], 999);
+                });
+            }
+        });
+        assert_eq!(shared.len(), 1000);
+        assert_eq!(shared.get(1000), None);
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    mem::MaybeUninit,
+    ops::{Index, IndexMut},
+    slice::{Iter, IterMut, from_raw_parts, from_raw_parts_mut},
+    fmt::Debug
+};
+use crate::heap_array::HeapArray;
+
+pub struct SmallArray<T, const N: usize> {
+    repr: Repr<T, N>
+}
+enum Repr<T, const N: usize> {
+    Inline {len: usize, data: [MaybeUninit<T>; N]},
+    Heap(HeapArray<T>)
+}
+impl<T: Clone, const N: usize> SmallArray<T, N> {
+    #[must_use]
+    pub fn new(value: T, len: usize) -> Self {
+        Self::from_exact_iter((0..len).map(|_| value.clone()))
+    }
+    #[must_use]
+    pub fn from_slice(slice: &[T]) -> Self {
+        Self::from_exact_iter(slice.iter().cloned())
+    }
+}
+impl<T, const N: usize> SmallArray<T, N> {
+    pub fn from_exact_iter<I: ExactSizeIterator<Item = T>>(iter: I) -> Self {
+        let len = iter.len();
+        if len > N {
+            return Self {repr: Repr::Heap(HeapArray::from_exact_iter(iter))};
+        }
+        let mut this = Self {
+            repr: Repr::Inline {len: 0, data: [const {MaybeUninit::uninit()}; N]}
+        };
+        if let Repr::Inline {len: init, data} = &mut this.repr {
+            for item in iter.take(len) {
+                data[*init] = MaybeUninit::new(item);
+                *init += 1;
+            }
+            if *init != len {
+                panic!("iterator yielded {} elements but reported a length of {}", *init, len);
+            }
+        }
+        this
+    }
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline {len, ..} => *len,
+            Repr::Heap(array) => array.len()
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    #[inline]
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline {..})
+    }
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        match &self.repr {
+            Repr::Inline {len, data} => unsafe {from_raw_parts(data.as_ptr() as *const T, *len)},
+            Repr::Heap(array) => array.as_slice()
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.repr {
+            Repr::Inline {len, data} => unsafe {from_raw_parts_mut(data.as_mut_ptr() as *mut T, *len)},
+            Repr::Heap(array) => array.as_mut_slice()
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_slice().iter()
+    }
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+impl<T, const N: usize> Drop for SmallArray<T, N> {
+    fn drop(&mut self) {
+        if let Repr::Inline {len, data} = &mut self.repr {
+            let init = *len;
+            *len = 0;
+            for slot in &mut data[..init] {
+                unsafe {slot.assume_init_drop();}
+            }
+        }
+    }
+}
+impl<T: Clone, const N: usize> Clone for SmallArray<T, N> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self.as_slice())
+    }
+}
+impl<T, const N: usize> From<HeapArray<T>> for SmallArray<T, N> {
+    fn from(array: HeapArray<T>) -> Self {
+        if array.len() > N {
+            Self {repr: Repr::Heap(array)}
+        }
+        else {
+            Self::from_exact_iter(array.into_iter())
+        }
+    }
+}
+impl<T, const N: usize> Index<usize> for SmallArray<T, N> {
+    type Output = T;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+impl<T, const N: usize> IndexMut<usize> for SmallArray<T, N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.as_mut_slice()[index]
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a SmallArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallArray<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<T: Debug, const N: usize> Debug for SmallArray<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::DropCounter;
+    use std::cell::Cell;
+
+    #[test]
+    fn spills_to_heap_past_inline_capacity() {
+        let inline = SmallArray::<i32, 4>::from_slice(&[1, 2, 3, 4]);
+        assert!(inline.is_inline());
+        assert_eq!(inline.as_slice(), &[1, 2, 3, 4]);
+        let spilled = SmallArray::<i32, 4>::from_slice(&[1, 2, 3, 4, 5]);
+        assert!(!spilled.is_inline());
+        assert_eq!(spilled.as_slice(), &[1, 2, 3, 4, 5]);
+        let empty = SmallArray::<i32, 0>::from_slice(&[]);
+        assert!(empty.is_inline() && empty.is_empty());
+        assert!(!SmallArray::<i32, 0>::new(1, 1).is_inline());
+    }
+    #[test]
+    fn access_matches_on_both_sides_of_the_boundary() {
+        for len in [3, 4, 5] {
+            let mut array = SmallArray::<String, 4>::new("x".to_string(), len);
+            assert_eq!(array.len(), len);
+            array[len - 1].push('y');
+            *array.get_mut(0).unwrap() = "a".to_string();
+            assert_eq!(array.get(len), None);
+            assert_eq!(array.iter().last().map(String::as_str), Some("xy"));
+            for item in &mut array {
+                item.push('!');
+            }
+            assert_eq!(array[0], "a!");
+            let copy = array.clone();
+            assert_eq!(copy.as_slice(), array.as_slice());
+            assert_eq!(copy.is_inline(), len <= 4);
+        }
+    }
+    #[test]
+    fn from_heap_array_keeps_large_buffers() {
+        let heap = HeapArray::from_slice(&[1, 2, 3]);
+        let ptr = heap.as_ptr();
+        let spilled = SmallArray::<i32, 2>::from(heap);
+        assert_eq!(spilled.as_slice().as_ptr(), ptr);
+        let inline = SmallArray::<i32, 4>::from(HeapArray::from_slice(&[1, 2, 3]));
+        assert!(inline.is_inline());
+        assert_eq!(format!("{:?}", inline), "[1, 2, 3]");
+    }
+    #[test]
+    fn drops_inline_elements_once() {
+        let drops = Cell::new(0);
+        let array = SmallArray::<DropCounter, 4>::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops)));
+        assert!(array.is_inline());
+        drop(array);
+        assert_eq!(drops.get(), 3);
+        let array = SmallArray::<DropCounter, 2>::from_exact_iter((0..3).map(|i| DropCounter::new(i, &drops)));
+        drop(array);
+        assert_eq!(drops.get(), 6);
+    }
+    #[test]
+    fn short_iterator_drops_written_inline_elements() {
+        struct Short<'a> {
+            next: i32,
+            drops: &'a Cell<usize>,
+        }
+        impl<'a> Iterator for Short<'a> {
+            type Item = DropCounter<'a>;
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.next == 2 {return None;}
+                self.next += 1;
+                Some(DropCounter::new(self.next, self.drops))
+            }
+        }
+        impl ExactSizeIterator for Short<'_> {
+            fn len(&self) -> usize {
+                3
+            }
+        }
+        let drops = Cell::new(0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            SmallArray::<DropCounter, 4>::from_exact_iter(Short {next: 0, drops: &drops})
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+    }
+}
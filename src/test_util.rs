@@ -0,0 +1,27 @@
+use std::cell::Cell;
+
+#[derive(Debug)]
+pub(crate) struct DropCounter<'a> {
+    pub(crate) value: i32,
+    drops: &'a Cell<usize>,
+}
+impl<'a> DropCounter<'a> {
+    pub(crate) fn new(value: i32, drops: &'a Cell<usize>) -> Self {
+        Self {value, drops}
+    }
+}
+impl Clone for DropCounter<'_> {
+    fn clone(&self) -> Self {
+        Self::new(self.value, self.drops)
+    }
+}
+impl PartialEq for DropCounter<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}